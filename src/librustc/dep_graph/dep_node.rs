@@ -499,8 +499,10 @@ define_dep_nodes!( <'tcx>
     [] AssociatedItemDefIds(DefId),
     [] InherentImpls(DefId),
     [] TypeckBodiesKrate,
+    [] ClosureUpvarTysKrate,
     [] TypeckTables(DefId),
     [] HasTypeckTables(DefId),
+    [] PathTraitImports(DefId),
     [] ConstEval { param_env: ParamEnvAnd<'tcx, (DefId, &'tcx Substs<'tcx>)> },
     [] SymbolName(DefId),
     [] InstanceSymbolName { instance: Instance<'tcx> },
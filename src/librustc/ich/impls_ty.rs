@@ -137,6 +137,12 @@ impl_stable_hash_for!(enum ty::BorrowKind {
     MutBorrow
 });
 
+impl_stable_hash_for!(enum ty::TryKind {
+    Option,
+    Result,
+    Other
+});
+
 impl<'gcx> HashStable<StableHashingContext<'gcx>>
 for ty::UpvarCapture<'gcx> {
     fn hash_stable<W: StableHasherResult>(&self,
@@ -504,6 +510,16 @@ impl_stable_hash_for!(struct ty::DebruijnIndex {
     depth
 });
 
+impl_stable_hash_for!(enum ty::lint_hint::LintHint {
+    CloneOnCopy,
+    OverflowingLiteral(value),
+    RedundantTypeAnnotation,
+    StringToString,
+    DerefOfBoxNew,
+    AsymmetricComparisonDeref,
+    FormatUnitArgument
+});
+
 impl_stable_hash_for!(enum ty::cast::CastKind {
     CoercionCast,
     PtrPtrCast,
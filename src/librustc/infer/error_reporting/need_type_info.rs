@@ -9,10 +9,12 @@
 // except according to those terms.
 
 use hir::{self, Local, Pat, Body, HirId};
+use hir::def::{CtorKind, Def};
 use hir::intravisit::{self, Visitor, NestedVisitorMap};
 use infer::InferCtxt;
 use infer::type_variable::TypeVariableOrigin;
 use ty::{self, Ty, TyInfer, TyVar};
+use errors::DiagnosticBuilder;
 use syntax_pos::Span;
 
 struct FindLocalByTypeVisitor<'a, 'gcx: 'a + 'tcx, 'tcx: 'a> {
@@ -21,6 +23,9 @@ struct FindLocalByTypeVisitor<'a, 'gcx: 'a + 'tcx, 'tcx: 'a> {
     hir_map: &'a hir::map::Map<'gcx>,
     found_local_pattern: Option<&'gcx Pat>,
     found_arg_pattern: Option<&'gcx Pat>,
+    /// The span the error was first reported at.
+    error_span: Span,
+    found_variant_field: Option<&'gcx hir::Expr>,
 }
 
 impl<'a, 'gcx, 'tcx> FindLocalByTypeVisitor<'a, 'gcx, 'tcx> {
@@ -68,6 +73,41 @@ impl<'a, 'gcx, 'tcx> Visitor<'gcx> for FindLocalByTypeVisitor<'a, 'gcx, 'tcx> {
         }
         intravisit::walk_body(self, body);
     }
+
+    fn visit_expr(&mut self, expr: &'gcx hir::Expr) {
+        // The bounds on the type parameters of an enum are checked at the
+        // path of a variant being constructed, so when one of its fields
+        // has an unknown type, the error is first reported at the path.
+        if self.found_variant_field.is_none() && expr.span.contains(self.error_span) {
+            let fields: Vec<&'gcx hir::Expr> = match expr.node {
+                hir::ExprStruct(hir::QPath::Resolved(_, ref path), ref fields, _) => {
+                    match path.def {
+                        Def::Variant(..) => fields.iter().map(|field| &*field.expr).collect(),
+                        _ => vec![],
+                    }
+                }
+                hir::ExprCall(ref callee, ref args) => {
+                    match callee.node {
+                        hir::ExprPath(hir::QPath::Resolved(_, ref path)) => {
+                            match path.def {
+                                Def::VariantCtor(_, CtorKind::Fn) => args.iter().collect(),
+                                _ => vec![],
+                            }
+                        }
+                        _ => vec![],
+                    }
+                }
+                _ => vec![],
+            };
+            for field in fields {
+                if self.node_matches_type(field.hir_id) {
+                    self.found_variant_field = Some(field);
+                    break;
+                }
+            }
+        }
+        intravisit::walk_expr(self, expr);
+    }
 }
 
 
@@ -86,7 +126,61 @@ impl<'a, 'gcx, 'tcx> InferCtxt<'a, 'gcx, 'tcx> {
         }
     }
 
+    // If `pattern` binds a standard library collection whose element type
+    // could not be inferred, as in `let v = Vec::new();`, suggests the
+    // annotation that would fix it, e.g. `let v: Vec<T>`.
+    fn collection_annotation_hint(&self, pattern: &Pat) -> Option<String> {
+        const COLLECTIONS: &[&str] = &["Vec", "VecDeque", "LinkedList", "BinaryHeap",
+                                       "HashMap", "HashSet", "BTreeMap", "BTreeSet"];
+
+        let name = match pattern.simple_name() {
+            Some(name) => name,
+            None => return None,
+        };
+        let ty = match self.in_progress_tables.and_then(|tables| {
+            tables.borrow().node_id_to_type_opt(pattern.hir_id)
+        }) {
+            Some(ty) => self.resolve_type_vars_if_possible(&ty),
+            None => return None,
+        };
+        let (adt_def, substs) = match ty.sty {
+            ty::TyAdt(adt_def, substs) => (adt_def, substs),
+            _ => return None,
+        };
+        let krate = self.tcx.crate_name(adt_def.did.krate);
+        let adt_name = self.tcx.item_name(adt_def.did);
+        if !(krate == "std" || krate == "alloc") ||
+           !COLLECTIONS.contains(&&*adt_name.as_str()) ||
+           !substs.types().any(|ty| ty.needs_infer()) {
+            return None;
+        }
+
+        // Type parameters with defaults, like the hasher of a `HashMap`,
+        // need not be written.
+        let params = self.tcx.generics_of(adt_def.did).types.iter()
+                         .filter(|param| !param.has_default)
+                         .map(|param| param.name.to_string())
+                         .collect::<Vec<_>>();
+        Some(format!("consider giving `{}` a type that names the element type{} of the `{}`, \
+                      as in `let {}: {}<{}>`",
+                     name,
+                     if params.len() == 1 { "" } else { "s" },
+                     adt_name,
+                     name,
+                     adt_name,
+                     params.join(", ")))
+    }
+
     pub fn need_type_info(&self, body_id: Option<hir::BodyId>, span: Span, ty: Ty<'tcx>) {
+        self.need_type_info_err(body_id, span, ty).emit();
+    }
+
+    /// Builds the error `need_type_info` reports, without emitting it.
+    pub fn need_type_info_err(&self,
+                              body_id: Option<hir::BodyId>,
+                              span: Span,
+                              ty: Ty<'tcx>)
+                              -> DiagnosticBuilder<'tcx> {
         let ty = self.resolve_type_vars_if_possible(&ty);
         let name = self.extract_type_name(&ty);
 
@@ -99,6 +193,8 @@ impl<'a, 'gcx, 'tcx> InferCtxt<'a, 'gcx, 'tcx> {
             hir_map: &self.tcx.hir,
             found_local_pattern: None,
             found_arg_pattern: None,
+            error_span: span,
+            found_variant_field: None,
         };
 
         if let Some(body_id) = body_id {
@@ -106,6 +202,13 @@ impl<'a, 'gcx, 'tcx> InferCtxt<'a, 'gcx, 'tcx> {
             local_visitor.visit_expr(expr);
         }
 
+        if let Some(field) = local_visitor.found_variant_field {
+            // Point at the field whose type could not be inferred, rather
+            // than at the path of the variant.
+            err_span = field.span;
+            labels[0].0 = field.span;
+        }
+
         if let Some(pattern) = local_visitor.found_arg_pattern {
             err_span = pattern.span;
             // We don't want to show the default label for closures.
@@ -144,6 +247,14 @@ impl<'a, 'gcx, 'tcx> InferCtxt<'a, 'gcx, 'tcx> {
             err.span_label(target_span, label_message);
         }
 
-        err.emit();
+        if self.tcx.sess.opts.debugging_opts.collection_annotation_hints {
+            if let Some(pattern) = local_visitor.found_local_pattern {
+                if let Some(help) = self.collection_annotation_hint(pattern) {
+                    err.help(&help);
+                }
+            }
+        }
+
+        err
     }
 }
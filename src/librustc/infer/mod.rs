@@ -834,6 +834,29 @@ impl<'a, 'gcx, 'tcx> InferCtxt<'a, 'gcx, 'tcx> {
         r
     }
 
+    /// Execute `f` as if the type variable `vid` had been inferred to be
+    /// `ty`, then unroll that and any bindings `f` creates. Returns `None`,
+    /// without calling `f`, if `vid` cannot be `ty`. This is only meant for
+    /// explaining errors, e.g. by showing the other types that a choice of
+    /// `ty` would lead to; the obligations that `ty` would have to meet are
+    /// not checked.
+    pub fn probe_as_if<R, F>(&self,
+                             vid: TyVid,
+                             ty: Ty<'tcx>,
+                             param_env: ty::ParamEnv<'tcx>,
+                             f: F)
+                             -> Option<R> where
+        F: FnOnce() -> R,
+    {
+        self.probe(|_| {
+            let cause = ObligationCause::dummy();
+            match self.at(&cause, param_env).eq(self.tcx.mk_var(vid), ty) {
+                Ok(InferOk { value: (), obligations: _ }) => Some(f()),
+                Err(_) => None,
+            }
+        })
+    }
+
     pub fn add_given(&self,
                      sub: ty::Region<'tcx>,
                      sup: ty::RegionVid)
@@ -67,6 +67,27 @@ pub enum TypeVariableOrigin {
     Generalized(ty::TyVid),
 }
 
+impl TypeVariableOrigin {
+    /// The span the variable was created for, if any.
+    pub fn span(&self) -> Option<Span> {
+        match *self {
+            TypeVariableOrigin::MiscVariable(span) |
+            TypeVariableOrigin::NormalizeProjectionType(span) |
+            TypeVariableOrigin::TypeInference(span) |
+            TypeVariableOrigin::TypeParameterDefinition(span, _) |
+            TypeVariableOrigin::TransformedUpvar(span) |
+            TypeVariableOrigin::SubstitutionPlaceholder(span) |
+            TypeVariableOrigin::AutoDeref(span) |
+            TypeVariableOrigin::AdjustmentType(span) |
+            TypeVariableOrigin::DivergingStmt(span) |
+            TypeVariableOrigin::DivergingBlockExpr(span) |
+            TypeVariableOrigin::DivergingFn(span) |
+            TypeVariableOrigin::LatticeVariable(span) => Some(span),
+            TypeVariableOrigin::Generalized(_) => None,
+        }
+    }
+}
+
 pub type TypeVariableMap = FxHashMap<ty::TyVid, TypeVariableOrigin>;
 
 struct TypeVariableData<'tcx> {
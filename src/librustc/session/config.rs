@@ -1101,6 +1101,34 @@ options! {DebuggingOptions, DebuggingSetter, basic_debugging_options,
         "enable ThinLTO when possible"),
     inline_in_all_cgus: Option<bool> = (None, parse_opt_bool, [TRACKED],
         "control whether #[inline] functions are in all cgus"),
+    writeback_err_stats: bool = (false, parse_bool, [UNTRACKED],
+        "report how many types fell back to the error type during writeback of each body"),
+    dump_closure_sigs: bool = (false, parse_bool, [UNTRACKED],
+        "emit a note with the resolved signature of each closure"),
+    writeback_emit_spans: bool = (false, parse_bool, [UNTRACKED],
+        "emit a note with the HIR id, kind and resolved type of every node with a type"),
+    writeback_err_detail: bool = (false, parse_bool, [UNTRACKED],
+        "report how many nodes of each body have the error type, or a type containing it"),
+    keep_unnormalized_tys: bool = (false, parse_bool, [UNTRACKED],
+        "keep the projection types written in qualified paths, before normalization"),
+    writeback_validate_lift: bool = (false, parse_bool, [UNTRACKED],
+        "report every value writeback fails to lift to the global type context, instead of \
+         aborting on the first"),
+    collection_annotation_hints: bool = (false, parse_bool, [UNTRACKED],
+        "suggest naming the element type when the type of a collection can't be inferred"),
+    precompute_sized: bool = (false, parse_bool, [UNTRACKED],
+        "record during writeback whether the type of each expression is `Sized`"),
+    dump_infer_vars: bool = (false, parse_bool, [UNTRACKED],
+        "dump the type each inference variable of a body resolved to during writeback"),
+    resolve_ambiguous_as: Option<String> = (None, parse_opt_string, [UNTRACKED],
+        "report the types the nodes of a body would have if its first unresolved type \
+         variable were the given primitive type"),
+    dump_mono_calls: bool = (false, parse_bool, [UNTRACKED],
+        "emit the callee and type substs of every call and fn item that writeback resolves"),
+    verify_writeback: bool = (false, parse_bool, [UNTRACKED],
+        "check that no inference variable is left in the tables built by writeback"),
+    assumed_bound_hints: bool = (false, parse_bool, [UNTRACKED],
+        "point out the bound that would let a qualified path type resolve, when it does not"),
 }
 
 pub fn default_lib_output() -> CrateType {
@@ -2607,6 +2635,32 @@ mod tests {
         assert_eq!(reference.dep_tracking_hash(), opts.dep_tracking_hash());
         opts.debugging_opts.dump_mir_dir = Some(String::from("abc"));
         assert_eq!(reference.dep_tracking_hash(), opts.dep_tracking_hash());
+        opts.debugging_opts.writeback_err_stats = true;
+        assert_eq!(reference.dep_tracking_hash(), opts.dep_tracking_hash());
+        opts.debugging_opts.dump_closure_sigs = true;
+        assert_eq!(reference.dep_tracking_hash(), opts.dep_tracking_hash());
+        opts.debugging_opts.writeback_emit_spans = true;
+        assert_eq!(reference.dep_tracking_hash(), opts.dep_tracking_hash());
+        opts.debugging_opts.writeback_err_detail = true;
+        assert_eq!(reference.dep_tracking_hash(), opts.dep_tracking_hash());
+        opts.debugging_opts.keep_unnormalized_tys = true;
+        assert_eq!(reference.dep_tracking_hash(), opts.dep_tracking_hash());
+        opts.debugging_opts.writeback_validate_lift = true;
+        assert_eq!(reference.dep_tracking_hash(), opts.dep_tracking_hash());
+        opts.debugging_opts.collection_annotation_hints = true;
+        assert_eq!(reference.dep_tracking_hash(), opts.dep_tracking_hash());
+        opts.debugging_opts.precompute_sized = true;
+        assert_eq!(reference.dep_tracking_hash(), opts.dep_tracking_hash());
+        opts.debugging_opts.dump_infer_vars = true;
+        assert_eq!(reference.dep_tracking_hash(), opts.dep_tracking_hash());
+        opts.debugging_opts.resolve_ambiguous_as = Some(String::from("u32"));
+        assert_eq!(reference.dep_tracking_hash(), opts.dep_tracking_hash());
+        opts.debugging_opts.dump_mono_calls = true;
+        assert_eq!(reference.dep_tracking_hash(), opts.dep_tracking_hash());
+        opts.debugging_opts.verify_writeback = true;
+        assert_eq!(reference.dep_tracking_hash(), opts.dep_tracking_hash());
+        opts.debugging_opts.assumed_bound_hints = true;
+        assert_eq!(reference.dep_tracking_hash(), opts.dep_tracking_hash());
 
         // Make sure changing a [TRACKED] option changes the hash
         opts = reference.clone();
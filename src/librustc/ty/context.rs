@@ -19,6 +19,7 @@ use middle;
 use hir::{TraitCandidate, HirId, ItemLocalId};
 use hir::def::{Def, Export};
 use hir::def_id::{CrateNum, DefId, DefIndex, LOCAL_CRATE};
+use hir::intravisit::{self, Visitor, NestedVisitorMap};
 use hir::map as hir_map;
 use hir::map::DefPathHash;
 use lint::{self, Lint};
@@ -32,7 +33,7 @@ use middle::resolve_lifetime::{self, ObjectLifetimeDefault};
 use middle::stability;
 use mir::Mir;
 use mir::transform::Passes;
-use ty::subst::{Kind, Substs};
+use ty::subst::{Kind, Subst, Substs};
 use ty::ReprOptions;
 use traits;
 use ty::{self, Ty, TypeAndMut};
@@ -46,7 +47,7 @@ use ty::layout::{Layout, TargetDataLayout};
 use ty::maps;
 use ty::steal::Steal;
 use ty::BindingMode;
-use util::nodemap::{NodeMap, NodeSet, DefIdSet, ItemLocalMap};
+use util::nodemap::{NodeMap, NodeSet, DefIdSet, ItemLocalMap, ItemLocalSet};
 use util::nodemap::{FxHashMap, FxHashSet};
 use rustc_data_structures::accumulate_vec::AccumulateVec;
 use rustc_data_structures::stable_hasher::{HashStable, hash_stable_hashmap,
@@ -70,7 +71,7 @@ use std::sync::Arc;
 use syntax::abi;
 use syntax::ast::{self, Name, NodeId};
 use syntax::attr;
-use syntax::codemap::MultiSpan;
+use syntax::codemap::{CompilerDesugaringKind, ExpnFormat, MultiSpan};
 use syntax::symbol::{Symbol, keywords};
 use syntax_pos::Span;
 
@@ -385,10 +386,45 @@ pub struct TypeckTables<'tcx> {
     /// *from* expression of the cast, not the cast itself.
     cast_kinds: ItemLocalMap<ty::cast::CastKind>,
 
+    /// For each qualified path type like `<T as Trait>::Assoc`, the
+    /// projection as written, before it was normalized to the type in
+    /// `node_types`. Only used for diagnostics, and only populated under
+    /// `-Z keep-unnormalized-tys`.
+    unnormalized_tys: ItemLocalMap<Ty<'tcx>>,
+
+    /// Maps each `?` expression, i.e. its desugared `match`, to the family
+    /// of its operand.
+    try_kinds: ItemLocalMap<ty::TryKind>,
+
+    /// Whether the resolved type of each expression is `Sized` in the
+    /// param-env of the body. Only filled in under `-Z precompute-sized`.
+    sized_exprs: ItemLocalMap<bool>,
+
+    /// The set of expressions that denote a place (an lvalue), as opposed
+    /// to a value. This is derived from the shape of the expression and
+    /// the resolution of its path, and is computed once during writeback
+    /// so that later passes need not re-classify expressions themselves.
+    place_exprs: ItemLocalSet,
+
+    /// The set of `&` and `&mut` expressions that reborrow through an
+    /// existing reference, like `&*r` or `&r.field` where `r` is a
+    /// reference, as opposed to borrowing a place afresh, like `&local`.
+    reborrow_exprs: ItemLocalSet,
+
+    /// Facts noticed during writeback that lints want to check, such as
+    /// `.clone()` calls on `Copy` types. See `ty::lint_hint`.
+    lint_hints: ItemLocalMap<Vec<ty::lint_hint::LintHint>>,
+
     /// Set of trait imports actually used in the method resolution.
     /// This is used for warning unused imports.
     pub used_trait_imports: DefIdSet,
 
+    /// The subset of `used_trait_imports` that resolved an associated item
+    /// path, like `Type::method`. An import that is in `used_trait_imports`
+    /// but not here was only needed for method calls, where the receiver
+    /// type (possibly found by autoderef) determined the trait.
+    pub path_trait_imports: DefIdSet,
+
     /// If any errors occurred while type-checking this body,
     /// this field will be set to `true`.
     pub tainted_by_errors: bool,
@@ -417,7 +453,14 @@ impl<'tcx> TypeckTables<'tcx> {
             liberated_fn_sigs: ItemLocalMap(),
             fru_field_types: ItemLocalMap(),
             cast_kinds: ItemLocalMap(),
+            unnormalized_tys: ItemLocalMap(),
+            try_kinds: ItemLocalMap(),
+            sized_exprs: ItemLocalMap(),
+            place_exprs: ItemLocalSet(),
+            reborrow_exprs: ItemLocalSet(),
+            lint_hints: ItemLocalMap(),
             used_trait_imports: DefIdSet(),
+            path_trait_imports: DefIdSet(),
             tainted_by_errors: false,
             free_region_map: FreeRegionMap::new(),
         }
@@ -561,6 +604,216 @@ impl<'tcx> TypeckTables<'tcx> {
             .or_else(|| self.expr_ty_opt(expr))
     }
 
+    /// Returns whether the adjustments of `expr` call `Deref::deref` or
+    /// `DerefMut::deref_mut`, like the deref coercion of a `&String` to
+    /// `&str`, rather than only dereferencing builtin pointers, like the
+    /// coercion of a `&&T` to `&T`.
+    pub fn has_overloaded_deref(&self, expr: &hir::Expr) -> bool {
+        self.expr_adjustments(expr).iter().any(|adj| {
+            match adj.kind {
+                ty::adjustment::Adjust::Deref(Some(_)) => true,
+                _ => false,
+            }
+        })
+    }
+
+    /// Returns the resolved type of the node `id` before and after its
+    /// adjustments, like `&[u8; 2]` and `&[u8]` for an unsizing coercion,
+    /// or `None` if the node has no adjustments.
+    pub fn coercion_endpoints(&self, id: hir::HirId) -> Option<(Ty<'tcx>, Ty<'tcx>)> {
+        validate_hir_id_for_typeck_tables(self.local_id_root, id, false);
+        let target = match self.adjustments.get(&id.local_id).and_then(|adjs| adjs.last()) {
+            Some(adjustment) => adjustment.target,
+            None => return None,
+        };
+        self.node_id_to_type_opt(id).map(|source| (source, target))
+    }
+
+    /// Returns the type of the `..base` expression of the functional record
+    /// update `expr`, or `None` if `expr` is not a struct expression with a
+    /// base. The types of the fields taken from the base are available in
+    /// `fru_field_types`.
+    pub fn fru_base_ty(&self, expr: &hir::Expr) -> Option<Ty<'tcx>> {
+        match expr.node {
+            hir::ExprStruct(_, _, Some(ref base)) => self.expr_ty_opt(base),
+            _ => None,
+        }
+    }
+
+    /// Returns the enum type matched on by the `match` expression `expr`,
+    /// looking through any references the scrutinee is behind. This is the
+    /// type the discriminant of the match is read from. Returns `None` if
+    /// `expr` is not a `match` or its scrutinee is not of enum type.
+    pub fn match_scrutinee_enum_ty(&self, expr: &hir::Expr) -> Option<Ty<'tcx>> {
+        let scrutinee = match expr.node {
+            hir::ExprMatch(ref scrutinee, ..) => scrutinee,
+            _ => return None,
+        };
+
+        let mut ty = match self.expr_ty_adjusted_opt(scrutinee) {
+            Some(ty) => ty,
+            None => return None,
+        };
+        while let TyRef(_, mt) = ty.sty {
+            ty = mt.ty;
+        }
+
+        match ty.sty {
+            TyAdt(def, _) if def.is_enum() => Some(ty),
+            _ => None,
+        }
+    }
+
+    /// Returns the output type of the operator applied by `expr`, if it is
+    /// a unary, binary or compound assignment operator. For an overloaded
+    /// operator this is the return type of the operator method that type
+    /// checking resolved, with the substs recorded for `expr`, normalized in
+    /// `param_env` with its regions erased. Compound assignment methods like
+    /// `AddAssign::add_assign` return `()`, so the output of an overloaded
+    /// `a += b` is `()`, as is the type of a builtin one. For a builtin
+    /// operator on scalars, whose overload writeback cleared, this is the
+    /// type of `expr` itself.
+    pub fn operator_output_ty<'a>(&self,
+                                  tcx: TyCtxt<'a, 'tcx, 'tcx>,
+                                  param_env: ty::ParamEnv<'tcx>,
+                                  expr: &hir::Expr)
+                                  -> Option<Ty<'tcx>> {
+        match expr.node {
+            hir::ExprUnary(hir::UnNeg, _) |
+            hir::ExprUnary(hir::UnNot, _) |
+            hir::ExprBinary(..) |
+            hir::ExprAssignOp(..) => {}
+            _ => return None,
+        }
+
+        match self.type_dependent_defs().get(expr.hir_id) {
+            Some(&Def::Method(def_id)) => {
+                let substs = self.node_substs(expr.hir_id);
+                let sig = tcx.fn_sig(def_id).subst(tcx, substs);
+                let output = tcx.erase_late_bound_regions(&sig.output());
+                Some(tcx.normalize_associated_type_in_env(&output, param_env))
+            }
+            _ => self.expr_ty_opt(expr),
+        }
+    }
+
+    /// Returns the types of the operands of the comparison `expr`, like
+    /// `a == b` or `a < b`, before any adjustments, or `None` if `expr` is
+    /// not a comparison. Overloaded comparisons take their operands by
+    /// reference, so the adjusted types are references to these.
+    pub fn comparison_operand_tys(&self, expr: &hir::Expr) -> Option<(Ty<'tcx>, Ty<'tcx>)> {
+        match expr.node {
+            hir::ExprBinary(op, ref lhs, ref rhs) if op.node.is_comparison() => {
+                Some((self.expr_ty(lhs), self.expr_ty(rhs)))
+            }
+            _ => None,
+        }
+    }
+
+    /// Returns the resolved `Range*` struct type that the range expression
+    /// `expr` (e.g. `a..b`, `a..` or `..=b`) was lowered to, or `None` if
+    /// `expr` is not a range with at least one endpoint. `..` on its own
+    /// is lowered to a path rather than a struct expression.
+    pub fn range_struct_ty(&self, expr: &hir::Expr) -> Option<Ty<'tcx>> {
+        match expr.node {
+            hir::ExprStruct(hir::QPath::Resolved(None, ref path), ..)
+                    if path.span.is_compiler_desugaring(CompilerDesugaringKind::DotFill) => {
+                self.expr_ty_opt(expr)
+            }
+            _ => None,
+        }
+    }
+
+    /// Returns the resolved element type of the `for` loop `expr`, i.e. the
+    /// type of its loop pattern, or `None` if `expr` is not a `for` loop.
+    /// `expr` is the block that `for <pat> in <head> { ... }` is lowered to:
+    ///
+    /// ```ignore
+    /// { let _result = match IntoIterator::into_iter(<head>) {
+    ///       mut iter => loop { let mut __next; match ... ; let <pat> = __next; ... }
+    ///   }; _result }
+    /// ```
+    pub fn for_loop_elem_ty(&self, expr: &hir::Expr) -> Option<Ty<'tcx>> {
+        let init = match expr.node {
+            hir::ExprBlock(ref block) => match block.stmts.first().map(|stmt| &stmt.node) {
+                Some(&hir::StmtDecl(ref decl, _)) => match decl.node {
+                    hir::DeclLocal(ref local) => local.init.as_ref(),
+                    hir::DeclItem(_) => None,
+                },
+                _ => None,
+            },
+            _ => None,
+        };
+
+        let loop_block = match init.map(|init| &init.node) {
+            Some(&hir::ExprMatch(_, ref arms, hir::MatchSource::ForLoopDesugar)) => {
+                match arms.first().map(|arm| &arm.body.node) {
+                    Some(&hir::ExprLoop(ref block, _, hir::LoopSource::ForLoop)) => block,
+                    _ => return None,
+                }
+            }
+            _ => return None,
+        };
+
+        loop_block.stmts.iter().filter_map(|stmt| match stmt.node {
+            hir::StmtDecl(ref decl, _) => match decl.node {
+                hir::DeclLocal(ref local) if local.init.is_some() &&
+                                             local.source == hir::LocalSource::ForLoopDesugar => {
+                    self.pat_ty_opt(&local.pat)
+                }
+                _ => None,
+            },
+            _ => None,
+        }).next()
+    }
+
+    /// Returns the `HirId` and resolved type of the tail expression of
+    /// `block`, or `None` if the block has no tail expression, e.g. because
+    /// it ends in a statement. The type is taken after adjustments, so that
+    /// it is the type recorded for the block itself.
+    pub fn block_tail_ty(&self, block: &hir::Block) -> Option<(hir::HirId, Ty<'tcx>)> {
+        block.expr.as_ref().map(|expr| (expr.hir_id, self.expr_ty_adjusted(expr)))
+    }
+
+    /// Returns the resolved types along the method call chain that ends in
+    /// `expr`, like `a.b().c()`, starting with the innermost receiver `a` and
+    /// ending with the type of `expr` itself. Receiver types are taken before
+    /// any autoref or autoderef. Returns an empty vector if `expr` is not a
+    /// method call.
+    pub fn method_chain_tys(&self, expr: &hir::Expr) -> Vec<Ty<'tcx>> {
+        let mut tys = vec![];
+        let mut expr = expr;
+        while let hir::ExprMethodCall(_, _, ref args) = expr.node {
+            tys.push(self.expr_ty(expr));
+            expr = &args[0];
+        }
+        if !tys.is_empty() {
+            tys.push(self.expr_ty(expr));
+        }
+        tys.reverse();
+        tys
+    }
+
+    /// Returns the resolved types of the argument patterns of a closure, in
+    /// order. `closure_body` is the body of the `ExprClosure`, as found in
+    /// the HIR map. The closure shares these tables with its enclosing body.
+    pub fn closure_arg_tys(&self, closure_body: &hir::Body) -> Vec<Ty<'tcx>> {
+        closure_body.arguments.iter().map(|arg| self.pat_ty(&arg.pat)).collect()
+    }
+
+    /// Returns the resolved types of `root` and of every expression and
+    /// pattern inside it, in pre-order. The bodies of closures inside
+    /// `root` are only included if `hir_map` is given; nested items and
+    /// constants, which have tables of their own, never are.
+    pub fn subtree_tys<'a, 'hir>(&'a self,
+                                 root: &'hir hir::Expr,
+                                 hir_map: Option<&'a hir_map::Map<'hir>>)
+                                 -> Vec<(hir::HirId, Ty<'tcx>)> {
+        let mut visitor = SubtreeTys { tables: self, hir_map, tys: vec![] };
+        visitor.visit_expr(root);
+        visitor.tys
+    }
+
     pub fn is_method_call(&self, expr: &hir::Expr) -> bool {
         // Only paths and method calls/overloaded operators have
         // entries in type_dependent_defs, ignore the former here.
@@ -589,6 +842,27 @@ impl<'tcx> TypeckTables<'tcx> {
         }
     }
 
+    /// Returns the `HirId` and resolved type of every variable declared in
+    /// the body, arguments and the variables of closures included, in
+    /// declaration order. A variable that shadows another one appears as a
+    /// separate entry. The type of a `ref` binding is the reference type.
+    pub fn local_tys(&self) -> Vec<(hir::HirId, Ty<'tcx>)> {
+        let local_id_root = self.local_id_root.expect("trying to list locals without a body");
+
+        // Every binding pattern has a binding mode, and `ItemLocalId`s are
+        // assigned in source order when lowering.
+        let mut local_ids: Vec<_> = self.pat_binding_modes.keys().cloned().collect();
+        local_ids.sort();
+
+        local_ids.into_iter().map(|local_id| {
+            let hir_id = hir::HirId {
+                owner: local_id_root.index,
+                local_id,
+            };
+            (hir_id, self.node_id_to_type(hir_id))
+        }).collect()
+    }
+
     pub fn pat_adjustments(&self) -> LocalTableInContext<Vec<Ty<'tcx>>> {
         LocalTableInContext {
             local_id_root: self.local_id_root,
@@ -608,6 +882,19 @@ impl<'tcx> TypeckTables<'tcx> {
         self.upvar_capture_map[&upvar_id]
     }
 
+    /// Returns the captures of the closure `closure_def_id` alone, as the
+    /// captured variable and how it is captured, in source order. Nested
+    /// closures share the tables of their enclosing body, but each of their
+    /// captures is keyed by the closure that makes it.
+    pub fn closure_captures(&self, closure_def_id: DefId)
+                            -> Vec<(hir::HirId, ty::UpvarCapture<'tcx>)> {
+        let mut captures: Vec<_> = self.upvar_capture_map.iter().filter(|&(upvar_id, _)| {
+            upvar_id.closure_expr_id == closure_def_id.index
+        }).map(|(upvar_id, &capture)| (upvar_id.var_id, capture)).collect();
+        captures.sort_by_key(|&(var_id, _)| var_id.local_id);
+        captures
+    }
+
     pub fn closure_tys(&self) -> LocalTableInContext<ty::PolyFnSig<'tcx>> {
         LocalTableInContext {
             local_id_root: self.local_id_root,
@@ -681,6 +968,191 @@ impl<'tcx> TypeckTables<'tcx> {
         }
     }
 
+    pub fn unnormalized_tys(&self) -> LocalTableInContext<Ty<'tcx>> {
+        LocalTableInContext {
+            local_id_root: self.local_id_root,
+            data: &self.unnormalized_tys
+        }
+    }
+
+    pub fn unnormalized_tys_mut(&mut self) -> LocalTableInContextMut<Ty<'tcx>> {
+        LocalTableInContextMut {
+            local_id_root: self.local_id_root,
+            data: &mut self.unnormalized_tys
+        }
+    }
+
+    pub fn try_kinds(&self) -> LocalTableInContext<ty::TryKind> {
+        LocalTableInContext {
+            local_id_root: self.local_id_root,
+            data: &self.try_kinds
+        }
+    }
+
+    pub fn try_kinds_mut(&mut self) -> LocalTableInContextMut<ty::TryKind> {
+        LocalTableInContextMut {
+            local_id_root: self.local_id_root,
+            data: &mut self.try_kinds
+        }
+    }
+
+    /// Returns whether the `?` expression `expr` applies to an `Option`, a
+    /// `Result` or another `Try` type, or `None` if `expr` is not a `?`.
+    pub fn try_kind(&self, expr: &hir::Expr) -> Option<ty::TryKind> {
+        self.try_kinds().get(expr.hir_id).cloned()
+    }
+
+    /// Returns the resolved types along the chain of calls and `?`s that
+    /// ends in `expr`, like `a()?.b()?`, starting with the innermost call.
+    /// Each element is the output type of a call and the type after the
+    /// `?` applied to it, which is the output type again for calls without
+    /// a `?`. The chain continues through method receivers and stops at
+    /// the first call that is not a method call; the innermost receiver is
+    /// not included unless it is a call. Returns an empty vector if `expr`
+    /// is neither a call nor a `?`.
+    pub fn try_chain_tys(&self, expr: &hir::Expr) -> Vec<(Ty<'tcx>, Ty<'tcx>)> {
+        // `e?` is desugared to `match Try::into_result(e) { .. }`.
+        fn try_operand(expr: &hir::Expr) -> Option<&hir::Expr> {
+            match expr.node {
+                hir::ExprMatch(ref discr, _, hir::MatchSource::TryDesugar) => {
+                    match discr.node {
+                        hir::ExprCall(_, ref args) if args.len() == 1 => Some(&args[0]),
+                        _ => None,
+                    }
+                }
+                _ => None,
+            }
+        }
+
+        let mut tys = vec![];
+        let mut expr = expr;
+        loop {
+            let post_ty = self.expr_ty(expr);
+            let call = try_operand(expr).unwrap_or(expr);
+            match call.node {
+                hir::ExprMethodCall(_, _, ref args) => {
+                    tys.push((self.expr_ty(call), post_ty));
+                    expr = &args[0];
+                }
+                hir::ExprCall(..) => {
+                    tys.push((self.expr_ty(call), post_ty));
+                    break;
+                }
+                _ => break,
+            }
+        }
+        tys.reverse();
+        tys
+    }
+
+    /// Returns the resolved type of the body of each arm of the match
+    /// `expr`, in source order, along with the resolved type of the match
+    /// itself. The arm types are taken before any coercion to the type of
+    /// the match. Returns `None` if `expr` is not a match.
+    pub fn match_arm_tys(&self, expr: &hir::Expr) -> Option<(Vec<Ty<'tcx>>, Ty<'tcx>)> {
+        match expr.node {
+            hir::ExprMatch(_, ref arms, _) => {
+                let arm_tys = arms.iter().map(|arm| self.expr_ty(&arm.body)).collect();
+                Some((arm_tys, self.expr_ty(expr)))
+            }
+            _ => None,
+        }
+    }
+
+    /// Returns the resolved element type of the array literal, like
+    /// `[a, b]` or `[a; n]`, or of the `vec!` invocation `expr`. Returns
+    /// `None` if `expr` is neither, or if its element type could not be
+    /// resolved, like for a `vec![]` whose type is an error.
+    pub fn collection_elem_ty(&self, expr: &hir::Expr) -> Option<Ty<'tcx>> {
+        let ty = match self.expr_ty_opt(expr) {
+            Some(ty) => ty,
+            None => return None,
+        };
+
+        // Every form of `vec!` expands to a single call returning the `Vec`.
+        let is_vec_macro = match expr.span.ctxt().outer().expn_info() {
+            Some(info) => match info.callee.format {
+                ExpnFormat::MacroBang(name) => name == "vec",
+                _ => false,
+            },
+            None => false,
+        };
+
+        let elem_ty = match (&expr.node, &ty.sty) {
+            (&hir::ExprArray(_), &TyArray(elem_ty, _)) |
+            (&hir::ExprRepeat(..), &TyArray(elem_ty, _)) => elem_ty,
+            (&hir::ExprCall(..), &TyAdt(_, substs)) if is_vec_macro => substs.type_at(0),
+            _ => return None,
+        };
+        if elem_ty.references_error() {
+            None
+        } else {
+            Some(elem_ty)
+        }
+    }
+
+    pub fn sized_exprs(&self) -> LocalTableInContext<bool> {
+        LocalTableInContext {
+            local_id_root: self.local_id_root,
+            data: &self.sized_exprs
+        }
+    }
+
+    pub fn sized_exprs_mut(&mut self) -> LocalTableInContextMut<bool> {
+        LocalTableInContextMut {
+            local_id_root: self.local_id_root,
+            data: &mut self.sized_exprs
+        }
+    }
+
+    /// Returns whether the type of `expr` is `Sized`, as computed during
+    /// writeback, or `None` if that was not precomputed.
+    pub fn expr_is_sized(&self, expr: &hir::Expr) -> Option<bool> {
+        self.sized_exprs().get(expr.hir_id).cloned()
+    }
+
+    /// Returns whether `expr` is a place expression, such as a local
+    /// variable, a static, a field access, a dereference or an index.
+    pub fn is_place_expr(&self, expr: &hir::Expr) -> bool {
+        validate_hir_id_for_typeck_tables(self.local_id_root, expr.hir_id, false);
+        self.place_exprs.contains(&expr.hir_id.local_id)
+    }
+
+    pub fn record_place_expr(&mut self, id: hir::HirId) {
+        validate_hir_id_for_typeck_tables(self.local_id_root, id, true);
+        self.place_exprs.insert(id.local_id);
+    }
+
+    /// Returns whether `expr` is a `&` or `&mut` expression that reborrows
+    /// through an existing reference, rather than borrowing a place afresh.
+    pub fn is_reborrow(&self, expr: &hir::Expr) -> bool {
+        validate_hir_id_for_typeck_tables(self.local_id_root, expr.hir_id, false);
+        self.reborrow_exprs.contains(&expr.hir_id.local_id)
+    }
+
+    pub fn record_reborrow(&mut self, id: hir::HirId) {
+        validate_hir_id_for_typeck_tables(self.local_id_root, id, true);
+        self.reborrow_exprs.insert(id.local_id);
+    }
+
+    pub fn lint_hints(&self) -> LocalTableInContext<Vec<ty::lint_hint::LintHint>> {
+        LocalTableInContext {
+            local_id_root: self.local_id_root,
+            data: &self.lint_hints
+        }
+    }
+
+    pub fn lint_hints_mut(&mut self) -> LocalTableInContextMut<Vec<ty::lint_hint::LintHint>> {
+        LocalTableInContextMut {
+            local_id_root: self.local_id_root,
+            data: &mut self.lint_hints
+        }
+    }
+
+    pub fn has_lint_hint(&self, id: hir::HirId, hint: ty::lint_hint::LintHint) -> bool {
+        self.lint_hints().get(id).map_or(false, |hints| hints.contains(&hint))
+    }
+
     pub fn generator_sigs(&self)
         -> LocalTableInContext<Option<ty::GenSig<'tcx>>>
     {
@@ -716,6 +1188,55 @@ impl<'tcx> TypeckTables<'tcx> {
             data: &mut self.generator_interiors,
         }
     }
+
+    /// Returns the resolved types that the generator `expr` holds across
+    /// its suspension points, in the order in which they become live, or
+    /// `None` if `expr` is not a generator. A generator is only `Send` if
+    /// all of these are, along with its upvars.
+    pub fn generator_interior_tys(&self, expr: &hir::Expr) -> Option<&'tcx Slice<Ty<'tcx>>> {
+        self.generator_interiors().get(expr.hir_id).map(|interior| interior.as_slice())
+    }
+}
+
+struct SubtreeTys<'a, 'hir: 'a, 'tcx: 'a> {
+    tables: &'a TypeckTables<'tcx>,
+    hir_map: Option<&'a hir_map::Map<'hir>>,
+    tys: Vec<(hir::HirId, Ty<'tcx>)>,
+}
+
+impl<'a, 'hir, 'tcx> SubtreeTys<'a, 'hir, 'tcx> {
+    fn record(&mut self, hir_id: hir::HirId) {
+        if let Some(ty) = self.tables.node_id_to_type_opt(hir_id) {
+            self.tys.push((hir_id, ty));
+        }
+    }
+}
+
+impl<'a, 'hir, 'tcx> Visitor<'hir> for SubtreeTys<'a, 'hir, 'tcx> {
+    fn nested_visit_map<'this>(&'this mut self) -> NestedVisitorMap<'this, 'hir> {
+        NestedVisitorMap::None
+    }
+
+    fn visit_nested_body(&mut self, id: hir::BodyId) {
+        let hir_map = match self.hir_map {
+            Some(hir_map) => hir_map,
+            None => return,
+        };
+        if let hir_map::NodeExpr(&hir::Expr { node: hir::ExprClosure(..), .. }) =
+                hir_map.get(hir_map.body_owner(id)) {
+            self.visit_body(hir_map.body(id));
+        }
+    }
+
+    fn visit_expr(&mut self, expr: &'hir hir::Expr) {
+        self.record(expr.hir_id);
+        intravisit::walk_expr(self, expr);
+    }
+
+    fn visit_pat(&mut self, pat: &'hir hir::Pat) {
+        self.record(pat.hir_id);
+        intravisit::walk_pat(self, pat);
+    }
 }
 
 impl<'gcx> HashStable<StableHashingContext<'gcx>> for TypeckTables<'gcx> {
@@ -737,8 +1258,15 @@ impl<'gcx> HashStable<StableHashingContext<'gcx>> for TypeckTables<'gcx> {
             ref fru_field_types,
 
             ref cast_kinds,
+            ref unnormalized_tys,
+            ref try_kinds,
+            ref sized_exprs,
+            ref place_exprs,
+            ref reborrow_exprs,
+            ref lint_hints,
 
             ref used_trait_imports,
+            ref path_trait_imports,
             tainted_by_errors,
             ref free_region_map,
             ref generator_sigs,
@@ -779,9 +1307,16 @@ impl<'gcx> HashStable<StableHashingContext<'gcx>> for TypeckTables<'gcx> {
             liberated_fn_sigs.hash_stable(hcx, hasher);
             fru_field_types.hash_stable(hcx, hasher);
             cast_kinds.hash_stable(hcx, hasher);
+            unnormalized_tys.hash_stable(hcx, hasher);
+            try_kinds.hash_stable(hcx, hasher);
+            sized_exprs.hash_stable(hcx, hasher);
+            place_exprs.hash_stable(hcx, hasher);
+            reborrow_exprs.hash_stable(hcx, hasher);
+            lint_hints.hash_stable(hcx, hasher);
             generator_sigs.hash_stable(hcx, hasher);
             generator_interiors.hash_stable(hcx, hasher);
             used_trait_imports.hash_stable(hcx, hasher);
+            path_trait_imports.hash_stable(hcx, hasher);
             tainted_by_errors.hash_stable(hcx, hasher);
             free_region_map.hash_stable(hcx, hasher);
         })
@@ -0,0 +1,52 @@
+// Copyright 2017 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+// Facts about the nodes of a body that are noticed while writeback
+// resolves their types and that lints want to check. They are recorded
+// in the `TypeckTables` so that lints do not have to repeat method
+// resolution or trait selection to rediscover them.
+
+/// A lint-relevant fact about a single node.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash, RustcEncodable, RustcDecodable)]
+pub enum LintHint {
+    /// A call to `Clone::clone` whose receiver type is `Copy`, which
+    /// could be replaced by a plain copy.
+    CloneOnCopy,
+
+    /// An integer literal whose value, given here, is out of range for
+    /// its resolved type. Only fixed-size integer types are checked, and
+    /// a literal that is directly negated may be one past the maximum of
+    /// a signed type (e.g. `-128i8`).
+    OverflowingLiteral(u128),
+
+    /// A `let` with a type annotation whose initializer would have the
+    /// annotated type without it. Recorded on the `hir::Local`.
+    RedundantTypeAnnotation,
+
+    /// A call to `ToString::to_string` whose receiver is already a
+    /// `String`, which could be replaced by a clone or a move.
+    StringToString,
+
+    /// A dereference of a `Box::new(..)` call, like `*Box::new(x)`, which
+    /// allocates only to read back the value it was given.
+    DerefOfBoxNew,
+
+    /// A comparison where one operand is auto-dereferenced to match the
+    /// type of the other but the other is not, like `x == &boxed` comparing
+    /// an `S` to the `S` in a `Box<S>` through `PartialEq<&S>`. Reborrows
+    /// of `&mut` operands are not counted as dereferences.
+    AsymmetricComparisonDeref,
+
+    /// An argument of `format!`, `println!` or another macro expanding to
+    /// `format_args!` whose type is `()`, which is usually the result of a
+    /// call returning nothing rather than the value meant to be printed.
+    /// Recorded on the argument expression.
+    FormatUnitArgument,
+}
@@ -133,6 +133,12 @@ impl<'tcx> QueryDescription for queries::typeck_item_bodies<'tcx> {
     }
 }
 
+impl<'tcx> QueryDescription for queries::closure_upvar_tys<'tcx> {
+    fn describe(_: TyCtxt, _: CrateNum) -> String {
+        format!("collecting the upvar types of all closures")
+    }
+}
+
 impl<'tcx> QueryDescription for queries::reachable_set<'tcx> {
     fn describe(_: TyCtxt, _: CrateNum) -> String {
         format!("reachability")
@@ -185,6 +185,17 @@ define_maps! { <'tcx>
 
     [] fn has_typeck_tables: HasTypeckTables(DefId) -> bool,
 
+    /// The trait imports that the body `DefId` used to resolve associated
+    /// item paths, like `Type::method`. The body's other used trait imports
+    /// were only needed by method calls.
+    [] fn path_trait_imports: PathTraitImports(DefId) -> Rc<DefIdSet>,
+
+    /// The resolved types of the upvars of every closure in the crate, in
+    /// the order of the closure's upvars, with the closures in the order of
+    /// their bodies. (Defined only for LOCAL_CRATE)
+    [] fn closure_upvar_tys: closure_upvar_tys_dep_node(CrateNum)
+        -> Rc<Vec<(DefId, Vec<Ty<'tcx>>)>>,
+
     [] fn coherent_trait: coherent_trait_dep_node((CrateNum, DefId)) -> (),
 
     [] fn borrowck: BorrowCheck(DefId) -> Rc<BorrowCheckResult>,
@@ -404,6 +415,10 @@ fn typeck_item_bodies_dep_node<'tcx>(_: CrateNum) -> DepConstructor<'tcx> {
     DepConstructor::TypeckBodiesKrate
 }
 
+fn closure_upvar_tys_dep_node<'tcx>(_: CrateNum) -> DepConstructor<'tcx> {
+    DepConstructor::ClosureUpvarTysKrate
+}
+
 fn const_eval_dep_node<'tcx>(param_env: ty::ParamEnvAnd<'tcx, (DefId, &'tcx Substs<'tcx>)>)
                              -> DepConstructor<'tcx> {
     DepConstructor::ConstEval { param_env }
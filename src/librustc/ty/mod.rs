@@ -96,6 +96,7 @@ pub mod fold;
 pub mod inhabitedness;
 pub mod item_path;
 pub mod layout;
+pub mod lint_hint;
 pub mod _match;
 pub mod maps;
 pub mod outlives;
@@ -652,6 +653,17 @@ pub struct UpvarBorrow<'tcx> {
 
 pub type UpvarCaptureMap<'tcx> = FxHashMap<UpvarId, UpvarCapture<'tcx>>;
 
+/// The family of the operand of a `?` expression, as resolved by writeback.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, RustcEncodable, RustcDecodable)]
+pub enum TryKind {
+    /// `?` on an `Option`, which returns `None` early.
+    Option,
+    /// `?` on a `Result`, which returns the converted `Err` early.
+    Result,
+    /// `?` on any other type implementing `Try`.
+    Other,
+}
+
 #[derive(Copy, Clone)]
 pub struct ClosureUpvar<'tcx> {
     pub def: Def,
@@ -1747,6 +1759,28 @@ impl<'a, 'gcx, 'tcx> AdtDef {
         }
     }
 
+    /// The resolved types of the explicit discriminant expressions of a
+    /// local enum, with the index of their variant. Each expression is
+    /// checked against the type of `self.repr.discr_type()`, so a type
+    /// differing from it means the expression had a type error.
+    /// Discriminants of other crates have no recorded types and are
+    /// skipped.
+    pub fn explicit_discriminant_tys(&self, tcx: TyCtxt<'a, 'gcx, 'tcx>)
+                                     -> Vec<(usize, Ty<'gcx>)> {
+        self.variants.iter().enumerate().filter_map(|(i, v)| {
+            let expr_did = match v.discr {
+                VariantDiscr::Explicit(expr_did) => expr_did,
+                VariantDiscr::Relative(_) => return None,
+            };
+            let node_id = match tcx.hir.as_local_node_id(expr_did) {
+                Some(node_id) => node_id,
+                None => return None,
+            };
+            let body = tcx.hir.body(tcx.hir.body_owned_by(node_id));
+            tcx.typeck_tables_of(expr_did).expr_ty_opt(&body.value).map(|ty| (i, ty))
+        }).collect()
+    }
+
     pub fn destructor(&self, tcx: TyCtxt<'a, 'gcx, 'tcx>) -> Option<Destructor> {
         tcx.adt_destructor(self.did)
     }
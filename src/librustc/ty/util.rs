@@ -10,6 +10,7 @@
 
 //! misc. type-system utilities too small to deserve their own file
 
+use hir;
 use hir::def_id::{DefId, LOCAL_CRATE};
 use hir::map::DefPathData;
 use ich::NodeIdHashingMode;
@@ -228,6 +229,21 @@ impl<'a, 'tcx> TyCtxt<'a, 'tcx, 'tcx> {
         });
         hasher.finish()
     }
+
+    /// Returns the locals of the body of `def_id`, as listed by
+    /// `TypeckTables::local_tys`, each with whether its type needs to be
+    /// dropped under the param-env of the body. A local that needs drop
+    /// gets drop glue run at the end of its scope unless it is moved out of.
+    pub fn local_drop_tys(self, def_id: DefId) -> Vec<(hir::HirId, Ty<'tcx>, bool)> {
+        let tables = self.typeck_tables_of(def_id);
+        let param_env = self.param_env(def_id);
+
+        tables.local_tys().into_iter().map(|(hir_id, ty)| {
+            // Whether a type needs drop does not depend on its regions.
+            let needs_drop = self.erase_regions(&ty).needs_drop(self, param_env);
+            (hir_id, ty, needs_drop)
+        }).collect()
+    }
 }
 
 impl<'a, 'gcx, 'tcx> TyCtxt<'a, 'gcx, 'tcx> {
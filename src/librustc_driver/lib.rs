@@ -107,6 +107,7 @@ pub mod profile;
 pub mod driver;
 pub mod pretty;
 pub mod target_features;
+pub mod typeck_snapshot;
 mod derive_registrar;
 
 const BUG_REPORT_URL: &'static str = "https://github.com/rust-lang/rust/blob/master/CONTRIBUTING.\
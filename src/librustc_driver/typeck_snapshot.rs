@@ -0,0 +1,167 @@
+// Copyright 2017 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+//! Snapshots of the types resolved by writeback, for use in inference
+//! regression tests.
+//!
+//! A snapshot maps the span of every node that received a type during
+//! writeback to a printed form of that type. Anonymous regions are
+//! erased before printing, so that the region numbering chosen by
+//! inference does not leak into the output and snapshots are stable
+//! from one run to the next.
+//!
+//! The types can also be checked against `//~ TYPE` annotations in the
+//! source, see `TypeSnapshot::check_type_annotations`.
+
+use rustc::hir;
+use rustc::ty::{self, Ty, TyCtxt};
+
+use std::fmt;
+
+/// The resolved types of all bodies in a crate, ordered by span.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct TypeSnapshot {
+    pub entries: Vec<SnapshotEntry>,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, PartialOrd, Ord)]
+pub struct SnapshotEntry {
+    /// The span of the node, as printed by the codemap.
+    pub span: String,
+    /// The line on which the node starts, starting at 1.
+    pub line: usize,
+    /// The canonicalized resolved type of the node.
+    pub ty: String,
+}
+
+impl TypeSnapshot {
+    /// Returns the types recorded for the given span, in the order in which
+    /// they appear in the snapshot.
+    pub fn types_at(&self, span: &str) -> Vec<&str> {
+        self.entries.iter()
+                    .filter(|entry| entry.span == span)
+                    .map(|entry| &entry.ty[..])
+                    .collect()
+    }
+
+    /// Returns the types of the nodes starting on the given line, in the
+    /// order in which they appear in the snapshot.
+    pub fn types_on_line(&self, line: usize) -> Vec<&str> {
+        self.entries.iter()
+                    .filter(|entry| entry.line == line)
+                    .map(|entry| &entry.ty[..])
+                    .collect()
+    }
+
+    pub fn contains_ty(&self, ty: &str) -> bool {
+        self.entries.iter().any(|entry| entry.ty == ty)
+    }
+
+    /// Checks the snapshot of a single-file crate against the `//~ TYPE`
+    /// annotations of its source `src`, returning a message for each
+    /// annotation that does not hold, in source order.
+    ///
+    /// An annotation `//~ TYPE ty` holds if a node starting on its line has
+    /// the type `ty`, as printed in the snapshot. Like the error
+    /// annotations of compiletest, `//~^ TYPE ty` refers to the line above,
+    /// with one `^` per line.
+    pub fn check_type_annotations(&self, src: &str) -> Vec<String> {
+        let mut failures = vec![];
+
+        for (i, text) in src.lines().enumerate() {
+            let annotation = match text.find("//~") {
+                Some(pos) => &text[pos + "//~".len()..],
+                None => continue,
+            };
+            let above = annotation.chars().take_while(|&c| c == '^').count();
+            let rest = annotation[above..].trim_left();
+            if !rest.starts_with("TYPE") || above > i {
+                continue;
+            }
+            let expected = rest["TYPE".len()..].trim();
+
+            let line = i + 1 - above;
+            let found = self.types_on_line(line);
+            if !found.contains(&expected) {
+                failures.push(format!("line {}: expected a node of type `{}`, found {:?}",
+                                      line, expected, found));
+            }
+        }
+
+        failures
+    }
+}
+
+impl fmt::Display for TypeSnapshot {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        for entry in &self.entries {
+            writeln!(f, "{}: {}", entry.span, entry.ty)?;
+        }
+        Ok(())
+    }
+}
+
+/// Builds a snapshot of the resolved types of every body in the local
+/// crate. Must be called after type checking has completed, e.g. from
+/// an `after_analysis` callback of the `CompileController`.
+pub fn resolved_type_snapshot<'a, 'tcx>(tcx: TyCtxt<'a, 'tcx, 'tcx>) -> TypeSnapshot {
+    let mut entries = vec![];
+
+    for body_owner_def_id in tcx.body_owners() {
+        // Closures share the tables of their enclosing body, so only
+        // visit each set of tables once.
+        if tcx.closure_base_def_id(body_owner_def_id) != body_owner_def_id {
+            continue;
+        }
+
+        let tables = tcx.typeck_tables_of(body_owner_def_id);
+        let owner = tables.local_id_root.expect("body tables without a `local_id_root`");
+
+        for (&local_id, &ty) in tables.node_types().iter() {
+            let hir_id = hir::HirId {
+                owner: owner.index,
+                local_id,
+            };
+            let node_id = tcx.hir.definitions().find_node_for_hir_id(hir_id);
+
+            // `hir::Arg` nodes have a type but are not in the HIR map. The
+            // type of an argument is also recorded for its pattern, which is.
+            if tcx.hir.find(node_id).is_none() {
+                continue;
+            }
+            let span = tcx.hir.span(node_id);
+
+            let codemap = tcx.sess.codemap();
+            entries.push((span.lo(), span.hi(), SnapshotEntry {
+                span: codemap.span_to_string(span),
+                line: codemap.lookup_char_pos(span.lo()).line,
+                ty: canonicalize_ty(tcx, ty),
+            }));
+        }
+    }
+
+    entries.sort();
+
+    TypeSnapshot {
+        entries: entries.into_iter().map(|(_, _, entry)| entry).collect(),
+    }
+}
+
+/// Prints `ty` with every anonymous region erased. Named regions are kept,
+/// as their names come from the source and are therefore stable.
+fn canonicalize_ty<'a, 'tcx>(tcx: TyCtxt<'a, 'tcx, 'tcx>, ty: Ty<'tcx>) -> String {
+    let ty = tcx.fold_regions(&ty, &mut false, |r, _| {
+        match *r {
+            ty::ReStatic | ty::ReEarlyBound(_) => r,
+            _ => tcx.types.re_erased,
+        }
+    });
+    ty.to_string()
+}
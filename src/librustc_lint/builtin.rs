@@ -33,6 +33,7 @@ use rustc::hir::def_id::DefId;
 use rustc::cfg;
 use rustc::ty::subst::Substs;
 use rustc::ty::{self, Ty};
+use rustc::ty::lint_hint::LintHint;
 use rustc::traits::{self, Reveal};
 use rustc::hir::map as hir_map;
 use util::nodemap::NodeSet;
@@ -1284,3 +1285,30 @@ impl<'a, 'tcx> LateLintPass<'a, 'tcx> for UnionsWithDropFields {
         }
     }
 }
+
+/// Lint for `let` type annotations that inference makes redundant.
+pub struct RedundantTypeAnnotations;
+
+declare_lint! {
+    REDUNDANT_TYPE_ANNOTATIONS,
+    Allow,
+    "detects `let` type annotations that give the type the initializer already has"
+}
+
+impl LintPass for RedundantTypeAnnotations {
+    fn get_lints(&self) -> LintArray {
+        lint_array!(REDUNDANT_TYPE_ANNOTATIONS)
+    }
+}
+
+impl<'a, 'tcx> LateLintPass<'a, 'tcx> for RedundantTypeAnnotations {
+    fn check_local(&mut self, cx: &LateContext, local: &hir::Local) {
+        if let Some(ref ty) = local.ty {
+            if cx.tables.has_lint_hint(local.hir_id, LintHint::RedundantTypeAnnotation) {
+                cx.span_lint(REDUNDANT_TYPE_ANNOTATIONS,
+                             ty.span,
+                             "type annotation is redundant, the initializer already has this type");
+            }
+        }
+    }
+}
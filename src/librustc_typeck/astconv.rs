@@ -79,6 +79,10 @@ pub trait AstConv<'gcx, 'tcx> {
     fn set_tainted_by_errors(&self);
 
     fn record_ty(&self, hir_id: hir::HirId, ty: Ty<'tcx>, span: Span);
+
+    /// Records the projection type written at `hir_id` as a qualified path,
+    /// before it is normalized, for use in diagnostics.
+    fn record_unnormalized_ty(&self, hir_id: hir::HirId, ty: Ty<'tcx>);
 }
 
 struct ConvertedBinding<'tcx> {
@@ -847,17 +851,21 @@ impl<'o, 'gcx: 'tcx, 'tcx> AstConv<'gcx, 'tcx>+'o {
         (ty, def)
     }
 
-    fn qpath_to_ty(&self,
-                   span: Span,
-                   opt_self_ty: Option<Ty<'tcx>>,
-                   item_def_id: DefId,
-                   trait_segment: &hir::PathSegment,
-                   item_segment: &hir::PathSegment)
-                   -> Ty<'tcx>
+    /// Converts the path `<T as Trait>::Item` resolved to the associated
+    /// type `item_def_id` to a projection type, without normalizing it.
+    fn qpath_to_projection_ty(&self,
+                              opt_self_ty: Option<Ty<'tcx>>,
+                              item_def_id: DefId,
+                              path: &hir::Path)
+                              -> Ty<'tcx>
     {
         let tcx = self.tcx();
+        let span = path.span;
         let trait_def_id = tcx.parent_def_id(item_def_id).unwrap();
 
+        self.prohibit_type_params(&path.segments[..path.segments.len()-2]);
+        let trait_segment = &path.segments[path.segments.len()-2];
+        let item_segment = path.segments.last().unwrap();
         self.prohibit_type_params(slice::ref_slice(item_segment));
 
         let self_ty = if let Some(ty) = opt_self_ty {
@@ -871,16 +879,16 @@ impl<'o, 'gcx: 'tcx, 'tcx> AstConv<'gcx, 'tcx>+'o {
             return tcx.types.err;
         };
 
-        debug!("qpath_to_ty: self_type={:?}", self_ty);
+        debug!("qpath_to_projection_ty: self_type={:?}", self_ty);
 
         let trait_ref = self.ast_path_to_mono_trait_ref(span,
                                                         trait_def_id,
                                                         self_ty,
                                                         trait_segment);
 
-        debug!("qpath_to_ty: trait_ref={:?}", trait_ref);
+        debug!("qpath_to_projection_ty: trait_ref={:?}", trait_ref);
 
-        self.normalize_ty(span, tcx.mk_projection(item_def_id, trait_ref.substs))
+        tcx.mk_projection(item_def_id, trait_ref.substs)
     }
 
     pub fn prohibit_type_params(&self, segments: &[hir::PathSegment]) {
@@ -968,12 +976,8 @@ impl<'o, 'gcx: 'tcx, 'tcx> AstConv<'gcx, 'tcx>+'o {
                 tcx.mk_self_type()
             }
             Def::AssociatedTy(def_id) => {
-                self.prohibit_type_params(&path.segments[..path.segments.len()-2]);
-                self.qpath_to_ty(span,
-                                 opt_self_ty,
-                                 def_id,
-                                 &path.segments[path.segments.len()-2],
-                                 path.segments.last().unwrap())
+                let projection_ty = self.qpath_to_projection_ty(opt_self_ty, def_id, path);
+                self.normalize_ty(span, projection_ty)
             }
             Def::PrimTy(prim_ty) => {
                 assert_eq!(opt_self_ty, None);
@@ -1085,7 +1089,13 @@ impl<'o, 'gcx: 'tcx, 'tcx> AstConv<'gcx, 'tcx>+'o {
                 let opt_self_ty = maybe_qself.as_ref().map(|qself| {
                     self.ast_ty_to_ty(qself)
                 });
-                self.def_to_ty(opt_self_ty, path, false)
+                if let Def::AssociatedTy(def_id) = path.def {
+                    let projection_ty = self.qpath_to_projection_ty(opt_self_ty, def_id, path);
+                    self.record_unnormalized_ty(ast_ty.hir_id, projection_ty);
+                    self.normalize_ty(path.span, projection_ty)
+                } else {
+                    self.def_to_ty(opt_self_ty, path, false)
+                }
             }
             hir::TyPath(hir::QPath::TypeRelative(ref qself, ref segment)) => {
                 debug!("ast_ty_to_ty: qself={:?} segment={:?}", qself, segment);
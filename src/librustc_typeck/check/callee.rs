@@ -197,7 +197,11 @@ impl<'a, 'gcx, 'tcx> FnCtxt<'a, 'gcx, 'tcx> {
                             expected: Expectation<'tcx>)
                             -> Ty<'tcx> {
         let (fn_sig, def_span) = match callee_ty.sty {
-            ty::TyFnDef(def_id, _) => {
+            ty::TyFnDef(def_id, substs) => {
+                if self.tcx.fn_sig(def_id).abi() == abi::Abi::RustIntrinsic &&
+                   self.tcx.item_name(def_id) == "transmute" {
+                    self.deferred_transmute_checks.borrow_mut().push((call_expr.span, substs));
+                }
                 (callee_ty.fn_sig(self.tcx), self.tcx.hir.span_if_local(def_id))
             }
             ty::TyFnPtr(sig) => (sig, None),
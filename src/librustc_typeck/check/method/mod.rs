@@ -361,7 +361,9 @@ impl<'a, 'gcx, 'tcx> FnCtxt<'a, 'gcx, 'tcx> {
         if let Some(import_id) = pick.import_id {
             let import_def_id = self.tcx.hir.local_def_id(import_id);
             debug!("used_trait_import: {:?}", import_def_id);
-            self.tables.borrow_mut().used_trait_imports.insert(import_def_id);
+            let mut tables = self.tables.borrow_mut();
+            tables.used_trait_imports.insert(import_def_id);
+            tables.path_trait_imports.insert(import_def_id);
         }
 
         let def = pick.item.def();
@@ -100,13 +100,13 @@ use rustc::ty::adjustment::{Adjust, Adjustment, AutoBorrow};
 use rustc::ty::fold::{BottomUpFolder, TypeFoldable};
 use rustc::ty::maps::Providers;
 use rustc::ty::util::{Representability, IntTypeExt};
-use errors::DiagnosticBuilder;
+use errors::{Diagnostic, DiagnosticBuilder};
 use require_c_abi_if_variadic;
 use session::{CompileIncomplete, Session};
 use TypeAndSubsts;
 use lint;
 use util::common::{ErrorReported, indenter};
-use util::nodemap::{DefIdMap, FxHashMap, NodeMap};
+use util::nodemap::{DefIdMap, DefIdSet, FxHashMap, NodeMap};
 
 use std::cell::{Cell, RefCell, Ref, RefMut};
 use std::collections::hash_map::Entry;
@@ -114,6 +114,7 @@ use std::cmp;
 use std::fmt::Display;
 use std::mem::replace;
 use std::ops::{self, Deref};
+use std::rc::Rc;
 use syntax::abi::Abi;
 use syntax::ast;
 use syntax::codemap::{self, original_sp, Spanned};
@@ -148,6 +149,7 @@ mod compare_method;
 mod generator_interior;
 mod intrinsic;
 mod op;
+pub mod tables_test;
 
 /// A wrapper for InferCtxt's `in_progress_tables` field.
 #[derive(Copy, Clone)]
@@ -205,6 +207,15 @@ pub struct Inherited<'a, 'gcx: 'a+'tcx, 'tcx: 'a> {
 
     deferred_cast_checks: RefCell<Vec<cast::CastCheck<'tcx>>>,
 
+    // Calls to `transmute`, whose type arguments must be known by the end
+    // of type checking for intrinsicck to compare their sizes.
+    deferred_transmute_checks: RefCell<Vec<(Span, &'tcx Substs<'tcx>)>>,
+
+    // Mismatches between `bool` and the partly inferred type of a
+    // condition. Writeback reports them with the type the condition was
+    // finally inferred to have.
+    deferred_condition_mismatches: RefCell<Vec<(Ty<'tcx>, Diagnostic)>>,
+
     deferred_generator_interiors: RefCell<Vec<(hir::BodyId, Ty<'tcx>)>>,
 
     // Anonymized types found in explicit return types and their
@@ -619,6 +630,8 @@ impl<'a, 'gcx, 'tcx> Inherited<'a, 'gcx, 'tcx> {
             locals: RefCell::new(NodeMap()),
             deferred_call_resolutions: RefCell::new(DefIdMap()),
             deferred_cast_checks: RefCell::new(Vec::new()),
+            deferred_transmute_checks: RefCell::new(Vec::new()),
+            deferred_condition_mismatches: RefCell::new(Vec::new()),
             deferred_generator_interiors: RefCell::new(Vec::new()),
             anon_types: RefCell::new(NodeMap()),
             implicit_region_bound,
@@ -734,11 +747,28 @@ fn typeck_item_bodies<'a, 'tcx>(tcx: TyCtxt<'a, 'tcx, 'tcx>, crate_num: CrateNum
     })?)
 }
 
+fn closure_upvar_tys<'a, 'tcx>(tcx: TyCtxt<'a, 'tcx, 'tcx>, crate_num: CrateNum)
+                               -> Rc<Vec<(DefId, Vec<Ty<'tcx>>)>>
+{
+    debug_assert!(crate_num == LOCAL_CRATE);
+    let upvar_tys = tcx.body_owners().filter_map(|def_id| {
+        match tcx.type_of(def_id).sty {
+            ty::TyClosure(closure_def_id, substs) => {
+                Some((def_id, substs.upvar_tys(closure_def_id, tcx).collect()))
+            }
+            _ => None,
+        }
+    }).collect();
+    Rc::new(upvar_tys)
+}
+
 pub fn provide(providers: &mut Providers) {
     *providers = Providers {
         typeck_item_bodies,
+        closure_upvar_tys,
         typeck_tables_of,
         has_typeck_tables,
+        path_trait_imports,
         closure_kind,
         generator_sig,
         adt_destructor,
@@ -746,6 +776,12 @@ pub fn provide(providers: &mut Providers) {
     };
 }
 
+fn path_trait_imports<'a, 'tcx>(tcx: TyCtxt<'a, 'tcx, 'tcx>,
+                                def_id: DefId)
+                                -> Rc<DefIdSet> {
+    Rc::new(tcx.typeck_tables_of(def_id).path_trait_imports.clone())
+}
+
 fn generator_sig<'a, 'tcx>(tcx: TyCtxt<'a, 'tcx, 'tcx>,
                           def_id: DefId)
                           -> Option<ty::PolyGenSig<'tcx>> {
@@ -855,6 +891,22 @@ fn typeck_tables_of<'a, 'tcx>(tcx: TyCtxt<'a, 'tcx, 'tcx>,
         return tcx.typeck_tables_of(outer_def_id);
     }
 
+    typeck_body(tcx, def_id, false).0
+}
+
+/// Type-checks the body of `def_id` again, and returns the "type annotations
+/// needed" errors that writeback buffered instead of emitting them. For
+/// `#[rustc_dump_buffered_writeback_errors]`.
+pub fn typeck_tables_of_buffered<'a, 'tcx>(tcx: TyCtxt<'a, 'tcx, 'tcx>,
+                                           def_id: DefId)
+                                           -> Vec<Diagnostic> {
+    typeck_body(tcx, def_id, true).1
+}
+
+fn typeck_body<'a, 'tcx>(tcx: TyCtxt<'a, 'tcx, 'tcx>,
+                         def_id: DefId,
+                         buffer_errors: bool)
+                         -> (&'tcx ty::TypeckTables<'tcx>, Vec<Diagnostic>) {
     let id = tcx.hir.as_local_node_id(def_id).unwrap();
     let span = tcx.hir.span(id);
 
@@ -864,7 +916,7 @@ fn typeck_tables_of<'a, 'tcx>(tcx: TyCtxt<'a, 'tcx, 'tcx>,
     });
     let body = tcx.hir.body(body_id);
 
-    let tables = Inherited::build(tcx, def_id).enter(|inh| {
+    let (tables, errors) = Inherited::build(tcx, def_id).enter(|inh| {
         let param_env = tcx.param_env(def_id);
         let fcx = if let Some(decl) = fn_decl {
             let fn_sig = tcx.fn_sig(def_id);
@@ -902,6 +954,7 @@ fn typeck_tables_of<'a, 'tcx>(tcx: TyCtxt<'a, 'tcx, 'tcx>,
         fcx.closure_analyze(body);
         fcx.select_obligations_where_possible();
         fcx.check_casts();
+        fcx.check_transmutes();
         fcx.resolve_generator_interiors(def_id);
         fcx.select_all_obligations_or_error();
 
@@ -911,14 +964,18 @@ fn typeck_tables_of<'a, 'tcx>(tcx: TyCtxt<'a, 'tcx, 'tcx>,
             fcx.regionck_expr(body);
         }
 
-        fcx.resolve_type_vars_in_body(body)
+        if buffer_errors {
+            fcx.resolve_type_vars_in_body_buffered(body)
+        } else {
+            (fcx.resolve_type_vars_in_body(body), vec![])
+        }
     });
 
     // Consistency check our TypeckTables instance can hold all ItemLocalIds
     // it will need to hold.
     assert_eq!(tables.local_id_root,
                Some(DefId::local(tcx.hir.definitions().node_to_hir_id(id).owner)));
-    tables
+    (tables, errors)
 }
 
 fn check_abi<'a, 'tcx>(tcx: TyCtxt<'a, 'tcx, 'tcx>, span: Span, abi: Abi) {
@@ -1679,6 +1736,13 @@ impl<'a, 'gcx, 'tcx> AstConv<'gcx, 'tcx> for FnCtxt<'a, 'gcx, 'tcx> {
     fn record_ty(&self, hir_id: hir::HirId, ty: Ty<'tcx>, _span: Span) {
         self.write_ty(hir_id, ty)
     }
+
+    fn record_unnormalized_ty(&self, hir_id: hir::HirId, ty: Ty<'tcx>) {
+        let debugging_opts = &self.tcx.sess.opts.debugging_opts;
+        if debugging_opts.keep_unnormalized_tys || debugging_opts.assumed_bound_hints {
+            self.tables.borrow_mut().unnormalized_tys_mut().insert(hir_id, ty);
+        }
+    }
 }
 
 /// Controls whether the arguments are tupled. This is used for the call
@@ -2103,6 +2167,32 @@ impl<'a, 'gcx, 'tcx> FnCtxt<'a, 'gcx, 'tcx> {
         }
     }
 
+    // Reports a `transmute` whose source or target type is still unknown at
+    // the call. This runs before ambiguous obligations are reported, which
+    // would otherwise report the same unknown type, through its `Sized`
+    // bound, without mentioning the `transmute`.
+    fn check_transmutes(&self) {
+        let mut deferred_transmute_checks = self.deferred_transmute_checks.borrow_mut();
+        for (span, substs) in deferred_transmute_checks.drain(..) {
+            if self.is_tainted_by_errors() {
+                continue;
+            }
+
+            let substs = self.resolve_type_vars_if_possible(&substs);
+            for (i, which) in ["source", "target"].iter().enumerate() {
+                let ty = substs.type_at(i);
+                if ty.has_infer_types() {
+                    self.need_type_info_err(None, span, ty)
+                        .span_label(span, format!("cannot infer the {} type of this `transmute`",
+                                                  which))
+                        .help("specify both types, as in `transmute::<Source, Target>(value)`")
+                        .emit();
+                    break;
+                }
+            }
+        }
+    }
+
     fn resolve_generator_interiors(&self, def_id: DefId) {
         let mut deferred_generator_interiors = self.deferred_generator_interiors.borrow_mut();
         for (body_id, witness) in deferred_generator_interiors.drain(..) {
@@ -2739,7 +2829,13 @@ impl<'a, 'gcx, 'tcx> FnCtxt<'a, 'gcx, 'tcx> {
                 }
                 _ => (),
             }
-            err.emit();
+            if expected_ty.is_bool() && self.resolve_type_vars_if_possible(&ty).needs_infer() {
+                self.set_tainted_by_errors();
+                self.deferred_condition_mismatches.borrow_mut().push((ty, (*err).clone()));
+                err.cancel();
+            } else {
+                err.emit();
+            }
         }
         ty
     }
@@ -0,0 +1,876 @@
+// Copyright 2017 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+//! For unit testing: functions annotated with one of the special
+//! `rustc_dump_*` attributes below get the contents of the matching part
+//! of their `TypeckTables` reported as errors, so that compile-fail tests
+//! can check what writeback recorded.
+
+use check::typeck_tables_of_buffered;
+use rustc::hir;
+use rustc::hir::def_id::LOCAL_CRATE;
+use rustc::hir::intravisit::{self, Visitor, NestedVisitorMap};
+use rustc::hir::itemlikevisit::ItemLikeVisitor;
+use rustc::middle::lang_items;
+use rustc::traits;
+use rustc::ty::{self, Ty, TyCtxt};
+use rustc::ty::util::IntTypeExt;
+
+pub fn test_tables<'a, 'tcx>(tcx: TyCtxt<'a, 'tcx, 'tcx>) {
+    tcx.hir.krate().visit_all_item_likes(&mut TablesTest { tcx });
+}
+
+struct TablesTest<'a, 'tcx: 'a> {
+    tcx: TyCtxt<'a, 'tcx, 'tcx>
+}
+
+impl<'a, 'tcx> ItemLikeVisitor<'tcx> for TablesTest<'a, 'tcx> {
+    fn visit_item(&mut self, item: &'tcx hir::Item) {
+        if let hir::ItemEnum(ref enum_def, _) = item.node {
+            let item_def_id = self.tcx.hir.local_def_id(item.id);
+            if self.tcx.has_attr(item_def_id, "rustc_dump_discriminant_tys") {
+                let adt_def = self.tcx.adt_def(item_def_id);
+                let repr_ty = adt_def.repr.discr_type().to_ty(self.tcx);
+                for (i, ty) in adt_def.explicit_discriminant_tys(self.tcx) {
+                    let body_id = enum_def.variants[i].node.disr_expr.unwrap();
+                    self.tcx.sess.span_err(self.tcx.hir.body(body_id).value.span,
+                                           &format!("discriminant `{}`, repr `{}`", ty, repr_ty));
+                }
+            }
+            return;
+        }
+
+        let body_id = match item.node {
+            hir::ItemFn(.., body_id) => body_id,
+            _ => return,
+        };
+        let item_def_id = self.tcx.hir.local_def_id(item.id);
+        let body = self.tcx.hir.body(body_id);
+
+        if self.tcx.has_attr(item_def_id, "rustc_dump_place_exprs") {
+            let tables = self.tcx.typeck_tables_of(item_def_id);
+            DumpPlaceExprs { tcx: self.tcx, tables }.visit_body(body);
+        }
+
+        if self.tcx.has_attr(item_def_id, "rustc_dump_match_enum_tys") {
+            let tables = self.tcx.typeck_tables_of(item_def_id);
+            DumpMatchEnumTys { tcx: self.tcx, tables }.visit_body(body);
+        }
+
+        if self.tcx.has_attr(item_def_id, "rustc_dump_lint_hints") {
+            let tables = self.tcx.typeck_tables_of(item_def_id);
+            DumpLintHints { tcx: self.tcx, tables }.visit_body(body);
+        }
+
+        if self.tcx.has_attr(item_def_id, "rustc_dump_fru_tys") {
+            let tables = self.tcx.typeck_tables_of(item_def_id);
+            DumpFruTys { tcx: self.tcx, tables }.visit_body(body);
+        }
+
+        if self.tcx.has_attr(item_def_id, "rustc_dump_operator_output_tys") {
+            let tables = self.tcx.typeck_tables_of(item_def_id);
+            let param_env = self.tcx.param_env(item_def_id);
+            DumpOperatorOutputTys { tcx: self.tcx, tables, param_env }.visit_body(body);
+        }
+
+        if self.tcx.has_attr(item_def_id, "rustc_dump_generator_interiors") {
+            let tables = self.tcx.typeck_tables_of(item_def_id);
+            let param_env = self.tcx.param_env(item_def_id);
+            DumpGeneratorInteriors { tcx: self.tcx, tables, param_env }.visit_body(body);
+        }
+
+        if self.tcx.has_attr(item_def_id, "rustc_dump_index_kinds") {
+            let tables = self.tcx.typeck_tables_of(item_def_id);
+            DumpIndexKinds { tcx: self.tcx, tables }.visit_body(body);
+        }
+
+        if self.tcx.has_attr(item_def_id, "rustc_dump_range_tys") {
+            let tables = self.tcx.typeck_tables_of(item_def_id);
+            DumpRangeTys { tcx: self.tcx, tables }.visit_body(body);
+        }
+
+        if self.tcx.has_attr(item_def_id, "rustc_dump_local_tys") {
+            let tables = self.tcx.typeck_tables_of(item_def_id);
+            for (i, (hir_id, ty)) in tables.local_tys().into_iter().enumerate() {
+                let node_id = self.tcx.hir.definitions().find_node_for_hir_id(hir_id);
+                self.tcx.sess.span_err(self.tcx.hir.span(node_id),
+                                       &format!("local #{} `{}`", i, ty));
+            }
+        }
+
+        if self.tcx.has_attr(item_def_id, "rustc_dump_trait_import_uses") {
+            let tables = self.tcx.typeck_tables_of(item_def_id);
+            let path_trait_imports = self.tcx.path_trait_imports(item_def_id);
+            for &import_def_id in &tables.used_trait_imports {
+                let kind = if path_trait_imports.contains(&import_def_id) {
+                    "paths"
+                } else {
+                    "method calls only"
+                };
+                self.tcx.sess.span_err(self.tcx.def_span(import_def_id),
+                                       &format!("trait import used for {}", kind));
+            }
+        }
+
+        if self.tcx.has_attr(item_def_id, "rustc_dump_local_drop_tys") {
+            for (hir_id, ty, needs_drop) in self.tcx.local_drop_tys(item_def_id) {
+                let node_id = self.tcx.hir.definitions().find_node_for_hir_id(hir_id);
+                let drop = if needs_drop { "needs drop" } else { "no drop" };
+                self.tcx.sess.span_err(self.tcx.hir.span(node_id),
+                                       &format!("local `{}`, {}", ty, drop));
+            }
+        }
+
+        if self.tcx.has_attr(item_def_id, "rustc_dump_for_loop_elem_tys") {
+            let tables = self.tcx.typeck_tables_of(item_def_id);
+            DumpForLoopElemTys { tcx: self.tcx, tables }.visit_body(body);
+        }
+
+        if self.tcx.has_attr(item_def_id, "rustc_dump_unnormalized_tys") {
+            let tables = self.tcx.typeck_tables_of(item_def_id);
+            DumpUnnormalizedTys { tcx: self.tcx, tables }.visit_body(body);
+        }
+
+        if self.tcx.has_attr(item_def_id, "rustc_dump_block_tail_tys") {
+            let tables = self.tcx.typeck_tables_of(item_def_id);
+            DumpBlockTailTys { tcx: self.tcx, tables }.visit_body(body);
+        }
+
+        if self.tcx.has_attr(item_def_id, "rustc_dump_reborrows") {
+            let tables = self.tcx.typeck_tables_of(item_def_id);
+            DumpReborrows { tcx: self.tcx, tables }.visit_body(body);
+        }
+
+        if self.tcx.has_attr(item_def_id, "rustc_dump_comparison_operand_tys") {
+            let tables = self.tcx.typeck_tables_of(item_def_id);
+            DumpComparisonOperandTys { tcx: self.tcx, tables }.visit_body(body);
+        }
+
+        if self.tcx.has_attr(item_def_id, "rustc_dump_closure_captures") {
+            let tables = self.tcx.typeck_tables_of(item_def_id);
+            DumpClosureCaptures { tcx: self.tcx, tables }.visit_body(body);
+        }
+
+        if self.tcx.has_attr(item_def_id, "rustc_dump_try_kinds") {
+            let tables = self.tcx.typeck_tables_of(item_def_id);
+            DumpTryKinds { tcx: self.tcx, tables }.visit_body(body);
+        }
+
+        if self.tcx.has_attr(item_def_id, "rustc_dump_method_chain_tys") {
+            let tables = self.tcx.typeck_tables_of(item_def_id);
+            DumpMethodChainTys { tcx: self.tcx, tables }.visit_body(body);
+        }
+
+        if self.tcx.has_attr(item_def_id, "rustc_dump_closure_arg_tys") {
+            let tables = self.tcx.typeck_tables_of(item_def_id);
+            DumpClosureArgTys { tcx: self.tcx, tables }.visit_body(body);
+        }
+
+        if self.tcx.has_attr(item_def_id, "rustc_dump_sized_exprs") {
+            let tables = self.tcx.typeck_tables_of(item_def_id);
+            DumpSizedExprs { tcx: self.tcx, tables }.visit_body(body);
+        }
+
+        if self.tcx.has_attr(item_def_id, "rustc_dump_subtree_tys") {
+            let tables = self.tcx.typeck_tables_of(item_def_id);
+            DumpSubtreeTys { tcx: self.tcx, tables }.visit_body(body);
+        }
+
+        if self.tcx.has_attr(item_def_id, "rustc_dump_try_chain_tys") {
+            let tables = self.tcx.typeck_tables_of(item_def_id);
+            DumpTryChainTys { tcx: self.tcx, tables }.visit_body(body);
+        }
+
+        if self.tcx.has_attr(item_def_id, "rustc_dump_match_arm_tys") {
+            let tables = self.tcx.typeck_tables_of(item_def_id);
+            DumpMatchArmTys { tcx: self.tcx, tables }.visit_body(body);
+        }
+
+        if self.tcx.has_attr(item_def_id, "rustc_dump_collection_elem_tys") {
+            let tables = self.tcx.typeck_tables_of(item_def_id);
+            DumpCollectionElemTys { tcx: self.tcx, tables }.visit_body(body);
+        }
+
+        if self.tcx.has_attr(item_def_id, "rustc_dump_overloaded_derefs") {
+            let tables = self.tcx.typeck_tables_of(item_def_id);
+            DumpOverloadedDerefs { tcx: self.tcx, tables }.visit_body(body);
+        }
+
+        if self.tcx.has_attr(item_def_id, "rustc_dump_coercion_endpoints") {
+            let tables = self.tcx.typeck_tables_of(item_def_id);
+            DumpCoercionEndpoints { tcx: self.tcx, tables }.visit_body(body);
+        }
+
+        // Reports the upvar types of every closure in the crate, not only of
+        // those in this function.
+        if self.tcx.has_attr(item_def_id, "rustc_dump_closure_upvar_tys") {
+            for &(closure_def_id, ref upvar_tys) in self.tcx.closure_upvar_tys(LOCAL_CRATE).iter() {
+                let upvar_tys: Vec<_> = upvar_tys.iter().map(|ty| format!("`{}`", ty)).collect();
+                self.tcx.sess.span_err(self.tcx.def_span(closure_def_id),
+                                       &format!("upvar types [{}]", upvar_tys.join(", ")));
+            }
+        }
+
+        if self.tcx.has_attr(item_def_id, "rustc_dump_anon_const_tys") {
+            DumpAnonConstTys { tcx: self.tcx, tables: None }.visit_body(body);
+        }
+    }
+
+    fn visit_trait_item(&mut self, _: &'tcx hir::TraitItem) { }
+    fn visit_impl_item(&mut self, _: &'tcx hir::ImplItem) { }
+}
+
+/// Reports the errors writeback buffers for functions annotated with
+/// `rustc_dump_buffered_writeback_errors`. This runs before the bodies are
+/// type-checked, as writeback does not report an error once others were
+/// emitted.
+pub fn test_buffered_writeback_errors<'a, 'tcx>(tcx: TyCtxt<'a, 'tcx, 'tcx>) {
+    tcx.hir.krate().visit_all_item_likes(&mut BufferedWritebackErrorsTest { tcx });
+}
+
+struct BufferedWritebackErrorsTest<'a, 'tcx: 'a> {
+    tcx: TyCtxt<'a, 'tcx, 'tcx>
+}
+
+impl<'a, 'tcx> ItemLikeVisitor<'tcx> for BufferedWritebackErrorsTest<'a, 'tcx> {
+    fn visit_item(&mut self, item: &'tcx hir::Item) {
+        if let hir::ItemFn(..) = item.node {
+            let item_def_id = self.tcx.hir.local_def_id(item.id);
+            if self.tcx.has_attr(item_def_id, "rustc_dump_buffered_writeback_errors") {
+                let err_count = self.tcx.sess.err_count();
+                let errors = typeck_tables_of_buffered(self.tcx, item_def_id);
+                if self.tcx.sess.err_count() != err_count {
+                    self.tcx.sess.span_err(item.span, "errors were emitted instead of buffered");
+                }
+                for error in errors {
+                    let message = error.message();
+                    self.tcx.sess.span_err(error.span, &format!("buffered: {}", message));
+                }
+            }
+        }
+    }
+
+    fn visit_trait_item(&mut self, _: &'tcx hir::TraitItem) { }
+    fn visit_impl_item(&mut self, _: &'tcx hir::ImplItem) { }
+}
+
+/// Reports whether the initializer of each `let` is a place or a value.
+struct DumpPlaceExprs<'a, 'tcx: 'a> {
+    tcx: TyCtxt<'a, 'tcx, 'tcx>,
+    tables: &'a ty::TypeckTables<'tcx>,
+}
+
+impl<'a, 'tcx> Visitor<'tcx> for DumpPlaceExprs<'a, 'tcx> {
+    fn nested_visit_map<'this>(&'this mut self) -> NestedVisitorMap<'this, 'tcx> {
+        NestedVisitorMap::None
+    }
+
+    fn visit_local(&mut self, local: &'tcx hir::Local) {
+        if let Some(ref init) = local.init {
+            let kind = if self.tables.is_place_expr(init) { "place" } else { "value" };
+            self.tcx.sess.span_err(init.span, &format!("{} expression", kind));
+        }
+        intravisit::walk_local(self, local);
+    }
+}
+
+/// Reports the enum type matched on by each `match`.
+struct DumpMatchEnumTys<'a, 'tcx: 'a> {
+    tcx: TyCtxt<'a, 'tcx, 'tcx>,
+    tables: &'a ty::TypeckTables<'tcx>,
+}
+
+impl<'a, 'tcx> Visitor<'tcx> for DumpMatchEnumTys<'a, 'tcx> {
+    fn nested_visit_map<'this>(&'this mut self) -> NestedVisitorMap<'this, 'tcx> {
+        NestedVisitorMap::None
+    }
+
+    fn visit_expr(&mut self, expr: &'tcx hir::Expr) {
+        if let hir::ExprMatch(ref scrutinee, ..) = expr.node {
+            match self.tables.match_scrutinee_enum_ty(expr) {
+                Some(ty) => self.tcx.sess.span_err(scrutinee.span,
+                                                   &format!("enum scrutinee `{}`", ty)),
+                None => self.tcx.sess.span_err(scrutinee.span, "non-enum scrutinee"),
+            }
+        }
+        intravisit::walk_expr(self, expr);
+    }
+}
+
+/// Reports the lint hints recorded for each expression.
+struct DumpLintHints<'a, 'tcx: 'a> {
+    tcx: TyCtxt<'a, 'tcx, 'tcx>,
+    tables: &'a ty::TypeckTables<'tcx>,
+}
+
+impl<'a, 'tcx> Visitor<'tcx> for DumpLintHints<'a, 'tcx> {
+    fn nested_visit_map<'this>(&'this mut self) -> NestedVisitorMap<'this, 'tcx> {
+        NestedVisitorMap::None
+    }
+
+    fn visit_expr(&mut self, expr: &'tcx hir::Expr) {
+        if let Some(hints) = self.tables.lint_hints().get(expr.hir_id) {
+            for hint in hints {
+                self.tcx.sess.span_err(expr.span, &format!("lint hint `{:?}`", hint));
+            }
+        }
+        intravisit::walk_expr(self, expr);
+    }
+}
+
+/// Reports the base type and the field types of each functional record
+/// update.
+struct DumpFruTys<'a, 'tcx: 'a> {
+    tcx: TyCtxt<'a, 'tcx, 'tcx>,
+    tables: &'a ty::TypeckTables<'tcx>,
+}
+
+impl<'a, 'tcx> Visitor<'tcx> for DumpFruTys<'a, 'tcx> {
+    fn nested_visit_map<'this>(&'this mut self) -> NestedVisitorMap<'this, 'tcx> {
+        NestedVisitorMap::None
+    }
+
+    fn visit_expr(&mut self, expr: &'tcx hir::Expr) {
+        if let Some(base_ty) = self.tables.fru_base_ty(expr) {
+            let field_tys = &self.tables.fru_field_types()[expr.hir_id];
+            self.tcx.sess.span_err(expr.span,
+                                   &format!("base `{}`, fields {:?}", base_ty, field_tys));
+        }
+        intravisit::walk_expr(self, expr);
+    }
+}
+
+/// Reports the output type of each operator expression, and whether the
+/// operator is overloaded.
+struct DumpOperatorOutputTys<'a, 'tcx: 'a> {
+    tcx: TyCtxt<'a, 'tcx, 'tcx>,
+    tables: &'a ty::TypeckTables<'tcx>,
+    param_env: ty::ParamEnv<'tcx>,
+}
+
+impl<'a, 'tcx> Visitor<'tcx> for DumpOperatorOutputTys<'a, 'tcx> {
+    fn nested_visit_map<'this>(&'this mut self) -> NestedVisitorMap<'this, 'tcx> {
+        NestedVisitorMap::None
+    }
+
+    fn visit_expr(&mut self, expr: &'tcx hir::Expr) {
+        if let Some(output_ty) = self.tables.operator_output_ty(self.tcx, self.param_env, expr) {
+            let kind = if self.tables.is_method_call(expr) { "overloaded" } else { "builtin" };
+            self.tcx.sess.span_err(expr.span,
+                                   &format!("{} operator output `{}`", kind, output_ty));
+        }
+        intravisit::walk_expr(self, expr);
+    }
+}
+
+/// Reports the types held across a suspension point by each generator that
+/// are not `Send`.
+struct DumpGeneratorInteriors<'a, 'tcx: 'a> {
+    tcx: TyCtxt<'a, 'tcx, 'tcx>,
+    tables: &'a ty::TypeckTables<'tcx>,
+    param_env: ty::ParamEnv<'tcx>,
+}
+
+impl<'a, 'tcx> Visitor<'tcx> for DumpGeneratorInteriors<'a, 'tcx> {
+    fn nested_visit_map<'this>(&'this mut self) -> NestedVisitorMap<'this, 'tcx> {
+        NestedVisitorMap::None
+    }
+
+    fn visit_expr(&mut self, expr: &'tcx hir::Expr) {
+        if let Some(interior_tys) = self.tables.generator_interior_tys(expr) {
+            let send_trait = self.tcx.require_lang_item(lang_items::SendTraitLangItem);
+            let param_env = self.param_env;
+            let non_send_tys: Vec<_> = self.tcx.infer_ctxt().enter(|infcx| {
+                interior_tys.iter().filter(|&&ty| {
+                    !traits::type_known_to_meet_bound(&infcx, param_env, ty, send_trait,
+                                                      expr.span)
+                }).map(|ty| ty.to_string()).collect()
+            });
+
+            if non_send_tys.is_empty() {
+                self.tcx.sess.span_err(expr.span, "all held types are `Send`");
+            }
+            for ty in non_send_tys {
+                self.tcx.sess.span_err(expr.span, &format!("holds non-`Send` type `{}`", ty));
+            }
+        }
+        intravisit::walk_expr(self, expr);
+    }
+}
+
+/// Reports whether each index expression is builtin or overloaded.
+struct DumpIndexKinds<'a, 'tcx: 'a> {
+    tcx: TyCtxt<'a, 'tcx, 'tcx>,
+    tables: &'a ty::TypeckTables<'tcx>,
+}
+
+impl<'a, 'tcx> Visitor<'tcx> for DumpIndexKinds<'a, 'tcx> {
+    fn nested_visit_map<'this>(&'this mut self) -> NestedVisitorMap<'this, 'tcx> {
+        NestedVisitorMap::None
+    }
+
+    fn visit_expr(&mut self, expr: &'tcx hir::Expr) {
+        if let hir::ExprIndex(..) = expr.node {
+            let kind = if self.tables.is_method_call(expr) { "overloaded" } else { "builtin" };
+            self.tcx.sess.span_err(expr.span, &format!("{} index", kind));
+        }
+        intravisit::walk_expr(self, expr);
+    }
+}
+
+/// Reports the `Range*` struct type of each range expression.
+struct DumpRangeTys<'a, 'tcx: 'a> {
+    tcx: TyCtxt<'a, 'tcx, 'tcx>,
+    tables: &'a ty::TypeckTables<'tcx>,
+}
+
+impl<'a, 'tcx> Visitor<'tcx> for DumpRangeTys<'a, 'tcx> {
+    fn nested_visit_map<'this>(&'this mut self) -> NestedVisitorMap<'this, 'tcx> {
+        NestedVisitorMap::None
+    }
+
+    fn visit_expr(&mut self, expr: &'tcx hir::Expr) {
+        if let Some(range_ty) = self.tables.range_struct_ty(expr) {
+            self.tcx.sess.span_err(expr.span, &format!("range `{}`", range_ty));
+        }
+        intravisit::walk_expr(self, expr);
+    }
+}
+
+/// Reports the element type of each `for` loop.
+struct DumpForLoopElemTys<'a, 'tcx: 'a> {
+    tcx: TyCtxt<'a, 'tcx, 'tcx>,
+    tables: &'a ty::TypeckTables<'tcx>,
+}
+
+impl<'a, 'tcx> Visitor<'tcx> for DumpForLoopElemTys<'a, 'tcx> {
+    fn nested_visit_map<'this>(&'this mut self) -> NestedVisitorMap<'this, 'tcx> {
+        NestedVisitorMap::None
+    }
+
+    fn visit_expr(&mut self, expr: &'tcx hir::Expr) {
+        if let Some(elem_ty) = self.tables.for_loop_elem_ty(expr) {
+            self.tcx.sess.span_err(expr.span, &format!("for loop element `{}`", elem_ty));
+        }
+        intravisit::walk_expr(self, expr);
+    }
+}
+
+/// Reports each qualified path type as written next to the type it was
+/// normalized to.
+struct DumpUnnormalizedTys<'a, 'tcx: 'a> {
+    tcx: TyCtxt<'a, 'tcx, 'tcx>,
+    tables: &'a ty::TypeckTables<'tcx>,
+}
+
+impl<'a, 'tcx> Visitor<'tcx> for DumpUnnormalizedTys<'a, 'tcx> {
+    fn nested_visit_map<'this>(&'this mut self) -> NestedVisitorMap<'this, 'tcx> {
+        NestedVisitorMap::None
+    }
+
+    fn visit_ty(&mut self, hir_ty: &'tcx hir::Ty) {
+        if let Some(&unnormalized_ty) = self.tables.unnormalized_tys().get(hir_ty.hir_id) {
+            let ty = self.tables.node_id_to_type(hir_ty.hir_id);
+            self.tcx.sess.span_err(hir_ty.span,
+                                   &format!("`{}` normalized to `{}`", unnormalized_ty, ty));
+        }
+        intravisit::walk_ty(self, hir_ty);
+    }
+}
+
+/// Reports the type of the tail expression of each block, along with the
+/// type of the block.
+struct DumpBlockTailTys<'a, 'tcx: 'a> {
+    tcx: TyCtxt<'a, 'tcx, 'tcx>,
+    tables: &'a ty::TypeckTables<'tcx>,
+}
+
+impl<'a, 'tcx> Visitor<'tcx> for DumpBlockTailTys<'a, 'tcx> {
+    fn nested_visit_map<'this>(&'this mut self) -> NestedVisitorMap<'this, 'tcx> {
+        NestedVisitorMap::None
+    }
+
+    fn visit_block(&mut self, block: &'tcx hir::Block) {
+        let block_ty = self.tables.node_id_to_type(block.hir_id);
+        let msg = match self.tables.block_tail_ty(block) {
+            Some((_, tail_ty)) => format!("block `{}` with tail `{}`", block_ty, tail_ty),
+            None => format!("block `{}` without tail", block_ty),
+        };
+        self.tcx.sess.span_err(block.span, &msg);
+        intravisit::walk_block(self, block);
+    }
+}
+
+/// Reports whether each `&` or `&mut` expression is a reborrow.
+struct DumpReborrows<'a, 'tcx: 'a> {
+    tcx: TyCtxt<'a, 'tcx, 'tcx>,
+    tables: &'a ty::TypeckTables<'tcx>,
+}
+
+impl<'a, 'tcx> Visitor<'tcx> for DumpReborrows<'a, 'tcx> {
+    fn nested_visit_map<'this>(&'this mut self) -> NestedVisitorMap<'this, 'tcx> {
+        NestedVisitorMap::None
+    }
+
+    fn visit_expr(&mut self, expr: &'tcx hir::Expr) {
+        if let hir::ExprAddrOf(..) = expr.node {
+            let kind = if self.tables.is_reborrow(expr) { "reborrow" } else { "fresh borrow" };
+            self.tcx.sess.span_err(expr.span, kind);
+        }
+        intravisit::walk_expr(self, expr);
+    }
+}
+
+/// Reports the unadjusted operand types of each comparison.
+struct DumpComparisonOperandTys<'a, 'tcx: 'a> {
+    tcx: TyCtxt<'a, 'tcx, 'tcx>,
+    tables: &'a ty::TypeckTables<'tcx>,
+}
+
+impl<'a, 'tcx> Visitor<'tcx> for DumpComparisonOperandTys<'a, 'tcx> {
+    fn nested_visit_map<'this>(&'this mut self) -> NestedVisitorMap<'this, 'tcx> {
+        NestedVisitorMap::None
+    }
+
+    fn visit_expr(&mut self, expr: &'tcx hir::Expr) {
+        if let Some((lhs_ty, rhs_ty)) = self.tables.comparison_operand_tys(expr) {
+            self.tcx.sess.span_err(expr.span,
+                                   &format!("comparison of `{}` with `{}`", lhs_ty, rhs_ty));
+        }
+        intravisit::walk_expr(self, expr);
+    }
+}
+
+/// Reports the captures of each closure, including nested closures, as
+/// found in the tables they share with the enclosing body.
+struct DumpClosureCaptures<'a, 'tcx: 'a> {
+    tcx: TyCtxt<'a, 'tcx, 'tcx>,
+    tables: &'a ty::TypeckTables<'tcx>,
+}
+
+impl<'a, 'tcx> Visitor<'tcx> for DumpClosureCaptures<'a, 'tcx> {
+    fn nested_visit_map<'this>(&'this mut self) -> NestedVisitorMap<'this, 'tcx> {
+        NestedVisitorMap::None
+    }
+
+    fn visit_nested_body(&mut self, id: hir::BodyId) {
+        let def_id = self.tcx.hir.body_owner_def_id(id);
+        if self.tcx.closure_base_def_id(def_id) == def_id {
+            return;
+        }
+
+        let body = self.tcx.hir.body(id);
+        self.visit_body(body);
+    }
+
+    fn visit_expr(&mut self, expr: &'tcx hir::Expr) {
+        if let hir::ExprClosure(..) = expr.node {
+            let closure_def_id = self.tcx.hir.local_def_id(expr.id);
+            for (var_id, capture) in self.tables.closure_captures(closure_def_id) {
+                let var_node_id = self.tcx.hir.definitions().find_node_for_hir_id(var_id);
+                let mode = match capture {
+                    ty::UpvarCapture::ByValue => "by value",
+                    ty::UpvarCapture::ByRef(ty::UpvarBorrow { kind: ty::MutBorrow, .. }) => {
+                        "by mut ref"
+                    }
+                    ty::UpvarCapture::ByRef(ty::UpvarBorrow { kind: ty::UniqueImmBorrow, .. }) => {
+                        "by unique ref"
+                    }
+                    ty::UpvarCapture::ByRef(_) => "by ref",
+                };
+                self.tcx.sess.span_err(expr.span, &format!("captures `{}` {}",
+                                                           self.tcx.hir.name(var_node_id),
+                                                           mode));
+            }
+        }
+        intravisit::walk_expr(self, expr);
+    }
+}
+
+/// Reports the family of the operand of each `?` expression.
+struct DumpTryKinds<'a, 'tcx: 'a> {
+    tcx: TyCtxt<'a, 'tcx, 'tcx>,
+    tables: &'a ty::TypeckTables<'tcx>,
+}
+
+impl<'a, 'tcx> Visitor<'tcx> for DumpTryKinds<'a, 'tcx> {
+    fn nested_visit_map<'this>(&'this mut self) -> NestedVisitorMap<'this, 'tcx> {
+        NestedVisitorMap::None
+    }
+
+    fn visit_expr(&mut self, expr: &'tcx hir::Expr) {
+        if let Some(kind) = self.tables.try_kind(expr) {
+            let kind = match kind {
+                ty::TryKind::Option => "an `Option`",
+                ty::TryKind::Result => "a `Result`",
+                ty::TryKind::Other => "another `Try` type",
+            };
+            self.tcx.sess.span_err(expr.span, &format!("`?` on {}", kind));
+        }
+        intravisit::walk_expr(self, expr);
+    }
+}
+
+/// Reports the types along the method call chain that initializes each
+/// `let`.
+struct DumpMethodChainTys<'a, 'tcx: 'a> {
+    tcx: TyCtxt<'a, 'tcx, 'tcx>,
+    tables: &'a ty::TypeckTables<'tcx>,
+}
+
+impl<'a, 'tcx> Visitor<'tcx> for DumpMethodChainTys<'a, 'tcx> {
+    fn nested_visit_map<'this>(&'this mut self) -> NestedVisitorMap<'this, 'tcx> {
+        NestedVisitorMap::None
+    }
+
+    fn visit_local(&mut self, local: &'tcx hir::Local) {
+        if let Some(ref init) = local.init {
+            let tys = self.tables.method_chain_tys(init);
+            if !tys.is_empty() {
+                let tys: Vec<_> = tys.iter().map(|ty| format!("`{}`", ty)).collect();
+                self.tcx.sess.span_err(init.span, &format!("chain {}", tys.join(" -> ")));
+            }
+        }
+        intravisit::walk_local(self, local);
+    }
+}
+
+/// Reports the argument types of each closure.
+struct DumpClosureArgTys<'a, 'tcx: 'a> {
+    tcx: TyCtxt<'a, 'tcx, 'tcx>,
+    tables: &'a ty::TypeckTables<'tcx>,
+}
+
+impl<'a, 'tcx> Visitor<'tcx> for DumpClosureArgTys<'a, 'tcx> {
+    fn nested_visit_map<'this>(&'this mut self) -> NestedVisitorMap<'this, 'tcx> {
+        NestedVisitorMap::None
+    }
+
+    fn visit_expr(&mut self, expr: &'tcx hir::Expr) {
+        if let hir::ExprClosure(_, _, body_id, _, _) = expr.node {
+            let body = self.tcx.hir.body(body_id);
+            let tys: Vec<_> = self.tables.closure_arg_tys(body).iter()
+                                         .map(|ty| format!("`{}`", ty))
+                                         .collect();
+            self.tcx.sess.span_err(expr.span, &format!("arguments {}", tys.join(", ")));
+        }
+        intravisit::walk_expr(self, expr);
+    }
+}
+
+/// Reports whether the type of each operand of `&` is `Sized`.
+struct DumpSizedExprs<'a, 'tcx: 'a> {
+    tcx: TyCtxt<'a, 'tcx, 'tcx>,
+    tables: &'a ty::TypeckTables<'tcx>,
+}
+
+impl<'a, 'tcx> Visitor<'tcx> for DumpSizedExprs<'a, 'tcx> {
+    fn nested_visit_map<'this>(&'this mut self) -> NestedVisitorMap<'this, 'tcx> {
+        NestedVisitorMap::None
+    }
+
+    fn visit_expr(&mut self, expr: &'tcx hir::Expr) {
+        if let hir::ExprAddrOf(_, ref operand) = expr.node {
+            let msg = match self.tables.expr_is_sized(operand) {
+                Some(true) => "sized",
+                Some(false) => "unsized",
+                None => "not precomputed",
+            };
+            self.tcx.sess.span_err(operand.span, msg);
+        }
+        intravisit::walk_expr(self, expr);
+    }
+}
+
+/// Reports the types in the initializer of each `let`, and, if they differ,
+/// the types including those in closure bodies.
+struct DumpSubtreeTys<'a, 'tcx: 'a> {
+    tcx: TyCtxt<'a, 'tcx, 'tcx>,
+    tables: &'a ty::TypeckTables<'tcx>,
+}
+
+impl<'a, 'tcx> Visitor<'tcx> for DumpSubtreeTys<'a, 'tcx> {
+    fn nested_visit_map<'this>(&'this mut self) -> NestedVisitorMap<'this, 'tcx> {
+        NestedVisitorMap::None
+    }
+
+    fn visit_local(&mut self, local: &'tcx hir::Local) {
+        if let Some(ref init) = local.init {
+            let tys = self.tables.subtree_tys(init, None);
+            let with_closures = self.tables.subtree_tys(init, Some(&self.tcx.hir));
+            let format_tys = |tys: &[(hir::HirId, Ty<'tcx>)]| {
+                tys.iter().map(|&(_, ty)| format!("`{}`", ty)).collect::<Vec<_>>().join(", ")
+            };
+            self.tcx.sess.span_err(init.span, &format!("subtree {}", format_tys(&tys)));
+            if with_closures != tys {
+                self.tcx.sess.span_err(init.span, &format!("with closure bodies {}",
+                                                           format_tys(&with_closures)));
+            }
+        }
+        intravisit::walk_local(self, local);
+    }
+}
+
+/// Reports the types along the chain of calls and `?`s in the initializer
+/// of each `let`.
+struct DumpTryChainTys<'a, 'tcx: 'a> {
+    tcx: TyCtxt<'a, 'tcx, 'tcx>,
+    tables: &'a ty::TypeckTables<'tcx>,
+}
+
+impl<'a, 'tcx> Visitor<'tcx> for DumpTryChainTys<'a, 'tcx> {
+    fn nested_visit_map<'this>(&'this mut self) -> NestedVisitorMap<'this, 'tcx> {
+        NestedVisitorMap::None
+    }
+
+    fn visit_local(&mut self, local: &'tcx hir::Local) {
+        if let Some(ref init) = local.init {
+            let tys: Vec<_> = self.tables.try_chain_tys(init).iter().map(|&(call_ty, post_ty)| {
+                format!("`{}` => `{}`", call_ty, post_ty)
+            }).collect();
+            self.tcx.sess.span_err(init.span, &format!("chain {}", tys.join(", ")));
+        }
+        intravisit::walk_local(self, local);
+    }
+}
+
+/// Reports the type of each arm of the match initializing a `let`, along
+/// with the type of the match.
+struct DumpMatchArmTys<'a, 'tcx: 'a> {
+    tcx: TyCtxt<'a, 'tcx, 'tcx>,
+    tables: &'a ty::TypeckTables<'tcx>,
+}
+
+impl<'a, 'tcx> Visitor<'tcx> for DumpMatchArmTys<'a, 'tcx> {
+    fn nested_visit_map<'this>(&'this mut self) -> NestedVisitorMap<'this, 'tcx> {
+        NestedVisitorMap::None
+    }
+
+    fn visit_local(&mut self, local: &'tcx hir::Local) {
+        if let Some(ref init) = local.init {
+            if let Some((arm_tys, match_ty)) = self.tables.match_arm_tys(init) {
+                let arm_tys: Vec<_> = arm_tys.iter().map(|ty| format!("`{}`", ty)).collect();
+                self.tcx.sess.span_err(init.span,
+                                       &format!("arms {} => `{}`", arm_tys.join(", "), match_ty));
+            }
+        }
+        intravisit::walk_local(self, local);
+    }
+}
+
+/// Reports the element type of the array literal or `vec!` initializing
+/// a `let`.
+struct DumpCollectionElemTys<'a, 'tcx: 'a> {
+    tcx: TyCtxt<'a, 'tcx, 'tcx>,
+    tables: &'a ty::TypeckTables<'tcx>,
+}
+
+impl<'a, 'tcx> Visitor<'tcx> for DumpCollectionElemTys<'a, 'tcx> {
+    fn nested_visit_map<'this>(&'this mut self) -> NestedVisitorMap<'this, 'tcx> {
+        NestedVisitorMap::None
+    }
+
+    fn visit_local(&mut self, local: &'tcx hir::Local) {
+        if let Some(ref init) = local.init {
+            let msg = match self.tables.collection_elem_ty(init) {
+                Some(elem_ty) => format!("element type `{}`", elem_ty),
+                None => "no element type".to_string(),
+            };
+            self.tcx.sess.span_err(init.span, &msg);
+        }
+        intravisit::walk_local(self, local);
+    }
+}
+
+/// Reports whether the adjustments of the initializer of each `let` call a
+/// `Deref` impl.
+struct DumpOverloadedDerefs<'a, 'tcx: 'a> {
+    tcx: TyCtxt<'a, 'tcx, 'tcx>,
+    tables: &'a ty::TypeckTables<'tcx>,
+}
+
+impl<'a, 'tcx> Visitor<'tcx> for DumpOverloadedDerefs<'a, 'tcx> {
+    fn nested_visit_map<'this>(&'this mut self) -> NestedVisitorMap<'this, 'tcx> {
+        NestedVisitorMap::None
+    }
+
+    fn visit_local(&mut self, local: &'tcx hir::Local) {
+        if let Some(ref init) = local.init {
+            let msg = if self.tables.has_overloaded_deref(init) {
+                "overloaded deref"
+            } else {
+                "no overloaded deref"
+            };
+            self.tcx.sess.span_err(init.span, msg);
+        }
+        intravisit::walk_local(self, local);
+    }
+}
+
+/// Reports the types the initializer of each `let` is coerced from and to.
+struct DumpCoercionEndpoints<'a, 'tcx: 'a> {
+    tcx: TyCtxt<'a, 'tcx, 'tcx>,
+    tables: &'a ty::TypeckTables<'tcx>,
+}
+
+impl<'a, 'tcx> Visitor<'tcx> for DumpCoercionEndpoints<'a, 'tcx> {
+    fn nested_visit_map<'this>(&'this mut self) -> NestedVisitorMap<'this, 'tcx> {
+        NestedVisitorMap::None
+    }
+
+    fn visit_local(&mut self, local: &'tcx hir::Local) {
+        if let Some(ref init) = local.init {
+            let msg = match self.tables.coercion_endpoints(init.hir_id) {
+                Some((source, target)) => format!("coerced from `{}` to `{}`", source, target),
+                None => "not coerced".to_string(),
+            };
+            self.tcx.sess.span_err(init.span, &msg);
+        }
+        intravisit::walk_local(self, local);
+    }
+}
+
+/// Reports the type of every expression in the anonymous constants (such
+/// as array lengths) nested in a body, including the closures they contain,
+/// as found in the tables of each constant.
+struct DumpAnonConstTys<'a, 'tcx: 'a> {
+    tcx: TyCtxt<'a, 'tcx, 'tcx>,
+    tables: Option<&'a ty::TypeckTables<'tcx>>,
+}
+
+impl<'a, 'tcx> Visitor<'tcx> for DumpAnonConstTys<'a, 'tcx> {
+    fn nested_visit_map<'this>(&'this mut self) -> NestedVisitorMap<'this, 'tcx> {
+        NestedVisitorMap::None
+    }
+
+    fn visit_nested_body(&mut self, id: hir::BodyId) {
+        // Closures share the tables of their enclosing body.
+        let def_id = self.tcx.hir.body_owner_def_id(id);
+        if self.tcx.closure_base_def_id(def_id) != def_id {
+            let body = self.tcx.hir.body(id);
+            self.visit_body(body);
+            return;
+        }
+
+        let outer_tables = self.tables.take();
+        self.tables = Some(self.tcx.typeck_tables_of(def_id));
+        let body = self.tcx.hir.body(id);
+        self.visit_body(body);
+        self.tables = outer_tables;
+    }
+
+    fn visit_expr(&mut self, expr: &'tcx hir::Expr) {
+        if let Some(tables) = self.tables {
+            self.tcx.sess.span_err(expr.span,
+                                   &format!("anon const expr `{}`", tables.expr_ty(expr)));
+        }
+        intravisit::walk_expr(self, expr);
+    }
+}
@@ -13,15 +13,24 @@
 // substitutions.
 
 use check::FnCtxt;
+use errors::{Diagnostic, DiagnosticBuilder};
 use rustc::hir;
+use rustc::hir::def::Def;
 use rustc::hir::def_id::{DefId, DefIndex};
 use rustc::hir::intravisit::{self, Visitor, NestedVisitorMap};
+use rustc::hir::map as hir_map;
 use rustc::infer::{InferCtxt};
-use rustc::ty::{self, Ty, TyCtxt};
+use rustc::traits;
+use rustc::ty::{self, Ty, TyCtxt, ToPredicate};
+use rustc::ty::adjustment::{Adjust, Adjustment};
 use rustc::ty::fold::{TypeFolder,TypeFoldable};
-use rustc::util::nodemap::DefIdSet;
+use rustc::ty::lint_hint::LintHint;
+use rustc::util::nodemap::{DefIdSet, FxHashMap};
 use syntax::ast;
+use syntax::codemap::ExpnFormat;
 use syntax_pos::Span;
+use std::cell::{Cell, RefCell};
+use std::fmt;
 use std::mem;
 
 ///////////////////////////////////////////////////////////////////////////
@@ -30,10 +39,25 @@ use std::mem;
 impl<'a, 'gcx, 'tcx> FnCtxt<'a, 'gcx, 'tcx> {
     pub fn resolve_type_vars_in_body(&self, body: &'gcx hir::Body)
                                      -> &'gcx ty::TypeckTables<'gcx> {
+        self.write_back_body(body, false).0
+    }
+
+    /// Like `resolve_type_vars_in_body`, but the "type annotations needed"
+    /// error for a type that could not be resolved is returned instead of
+    /// being emitted, for callers that present diagnostics themselves.
+    /// Other errors found during writeback are still emitted.
+    pub fn resolve_type_vars_in_body_buffered(&self, body: &'gcx hir::Body)
+                                              -> (&'gcx ty::TypeckTables<'gcx>, Vec<Diagnostic>) {
+        self.write_back_body(body, true)
+    }
+
+    fn write_back_body(&self, body: &'gcx hir::Body, buffer_errors: bool)
+                       -> (&'gcx ty::TypeckTables<'gcx>, Vec<Diagnostic>) {
         let item_id = self.tcx.hir.body_owner(body.id());
         let item_def_id = self.tcx.hir.local_def_id(item_id);
 
-        let mut wbcx = WritebackCx::new(self, body);
+        let mut wbcx = WritebackCx::new(self, body, buffer_errors);
+        wbcx.report_condition_mismatches();
         for arg in &body.arguments {
             wbcx.visit_node_id(arg.pat.span, arg.hir_id);
         }
@@ -44,6 +68,7 @@ impl<'a, 'gcx, 'tcx> FnCtxt<'a, 'gcx, 'tcx> {
         wbcx.visit_fru_field_types();
         wbcx.visit_anon_types();
         wbcx.visit_cast_types();
+        wbcx.visit_unnormalized_tys();
         wbcx.visit_free_region_map();
         wbcx.visit_generator_sigs();
         wbcx.visit_generator_interiors();
@@ -53,9 +78,57 @@ impl<'a, 'gcx, 'tcx> FnCtxt<'a, 'gcx, 'tcx> {
         debug!("used_trait_imports({:?}) = {:?}", item_def_id, used_trait_imports);
         wbcx.tables.used_trait_imports = used_trait_imports;
 
+        let path_trait_imports = mem::replace(&mut self.tables.borrow_mut().path_trait_imports,
+                                              DefIdSet());
+        wbcx.tables.path_trait_imports = path_trait_imports;
+
         wbcx.tables.tainted_by_errors = self.is_tainted_by_errors();
 
-        self.tcx.alloc_tables(wbcx.tables)
+        #[cfg(debug_assertions)]
+        {
+            wbcx.audit_arg_types(item_id);
+            wbcx.audit_closure_anon_type(item_id);
+            wbcx.audit_cast_kinds();
+            wbcx.audit_pat_adjustments();
+            wbcx.audit_closure_kinds();
+            wbcx.audit_fru_field_types();
+            wbcx.audit_node_substs_regions();
+        }
+
+        if self.tcx.sess.opts.debugging_opts.writeback_err_stats {
+            wbcx.report_err_stats(item_def_id);
+        }
+
+        if self.tcx.sess.opts.debugging_opts.writeback_err_detail {
+            wbcx.report_err_detail(item_def_id);
+        }
+
+        if self.tcx.sess.opts.debugging_opts.dump_closure_sigs {
+            wbcx.report_closure_sigs();
+        }
+
+        if self.tcx.sess.opts.debugging_opts.writeback_emit_spans {
+            wbcx.report_node_spans();
+        }
+
+        if self.tcx.sess.opts.debugging_opts.dump_infer_vars {
+            wbcx.report_infer_vars();
+        }
+
+        if let Some(ref ty_name) = self.tcx.sess.opts.debugging_opts.resolve_ambiguous_as {
+            wbcx.report_counterfactual_tys(ty_name);
+        }
+
+        if self.tcx.sess.opts.debugging_opts.verify_writeback {
+            wbcx.verify_no_infer_vars();
+        }
+
+        if self.tcx.sess.opts.debugging_opts.assumed_bound_hints {
+            wbcx.report_assumed_bound_hints();
+        }
+
+        let buffered_errors = wbcx.buffered_errors.into_inner().unwrap_or_default();
+        (self.tcx.alloc_tables(wbcx.tables), buffered_errors)
     }
 }
 
@@ -73,10 +146,27 @@ struct WritebackCx<'cx, 'gcx: 'cx+'tcx, 'tcx: 'cx> {
     tables: ty::TypeckTables<'gcx>,
 
     body: &'gcx hir::Body,
+
+    /// The number of types that could not be fully resolved and were
+    /// replaced with `types.err`, for `-Z writeback-err-stats`.
+    err_ty_count: Cell<usize>,
+
+    /// The type each inference variable seen while resolving was resolved
+    /// to, for `-Z dump-infer-vars`.
+    infer_vars: RefCell<FxHashMap<ty::TyVid, Ty<'tcx>>>,
+
+    /// The errors about types that could not be resolved, if they are
+    /// returned to the caller rather than emitted.
+    buffered_errors: RefCell<Option<Vec<Diagnostic>>>,
+
+    /// The default binding modes of the patterns being visited, innermost
+    /// last, for `audit_pat_binding_mode`.
+    #[cfg(debug_assertions)]
+    pat_default_bms: Vec<ty::BindingMode>,
 }
 
 impl<'cx, 'gcx, 'tcx> WritebackCx<'cx, 'gcx, 'tcx> {
-    fn new(fcx: &'cx FnCtxt<'cx, 'gcx, 'tcx>, body: &'gcx hir::Body)
+    fn new(fcx: &'cx FnCtxt<'cx, 'gcx, 'tcx>, body: &'gcx hir::Body, buffer_errors: bool)
         -> WritebackCx<'cx, 'gcx, 'tcx>
     {
         let owner = fcx.tcx.hir.definitions().node_to_hir_id(body.id().node_id);
@@ -85,6 +175,11 @@ impl<'cx, 'gcx, 'tcx> WritebackCx<'cx, 'gcx, 'tcx> {
             fcx,
             tables: ty::TypeckTables::empty(Some(DefId::local(owner.owner))),
             body,
+            err_ty_count: Cell::new(0),
+            infer_vars: RefCell::new(FxHashMap()),
+            buffered_errors: RefCell::new(if buffer_errors { Some(vec![]) } else { None }),
+            #[cfg(debug_assertions)]
+            pat_default_bms: vec![],
         }
     }
 
@@ -92,6 +187,664 @@ impl<'cx, 'gcx, 'tcx> WritebackCx<'cx, 'gcx, 'tcx> {
         self.fcx.tcx
     }
 
+    /// The param-env of the body, for trait queries on resolved types.
+    fn param_env(&self) -> ty::ParamEnv<'gcx> {
+        self.tcx().lift_to_global(&self.fcx.param_env)
+                  .expect("the param-env of a body is always global")
+    }
+
+    fn report_err_stats(&self, item_def_id: DefId) {
+        let node_count = self.tables.node_types().iter().len();
+        self.tcx().sess.span_note_without_error(
+            self.body.value.span,
+            &format!("writeback-err-stats: `{}`: {} types fell back to `[type error]` \
+                      while resolving {} nodes{}",
+                     self.tcx().item_path_str(item_def_id),
+                     self.err_ty_count.get(),
+                     node_count,
+                     if self.tables.tainted_by_errors { " (tainted by errors)" } else { "" }));
+    }
+
+    // Unlike `report_err_stats`, which counts the types writeback itself
+    // replaced, this looks at the final node types, so it also counts
+    // error types that type checking produced while recovering.
+    fn report_err_detail(&self, item_def_id: DefId) {
+        let mut err_nodes = 0;
+        let mut referencing_nodes = 0;
+        for (_, &ty) in self.tables.node_types().iter() {
+            if ty == self.tcx().types.err {
+                err_nodes += 1;
+            } else if ty.references_error() {
+                referencing_nodes += 1;
+            }
+        }
+
+        self.tcx().sess.span_note_without_error(
+            self.body.value.span,
+            &format!("writeback-err-detail: `{}`: {} nodes have type `[type error]`, \
+                      {} other nodes have types that reference it",
+                     self.tcx().item_path_str(item_def_id),
+                     err_nodes,
+                     referencing_nodes));
+    }
+
+    // Emits the resolved signature of each closure in the body, for
+    // `-Z dump-closure-sigs`. Regions other than `'static` and the named
+    // parameters of the enclosing item are erased, so that the output does
+    // not depend on the scopes chosen by region inference.
+    fn report_closure_sigs(&self) {
+        let tcx = self.tcx().global_tcx();
+        let mut sigs = vec![];
+
+        for (&local_id, fn_sig) in self.tables.liberated_fn_sigs().iter() {
+            let hir_id = hir::HirId {
+                owner: self.tables.local_id_root.unwrap().index,
+                local_id,
+            };
+            if !self.tables.closure_tys().contains_key(hir_id) {
+                continue;
+            }
+
+            let fn_sig = tcx.fold_regions(fn_sig, &mut false, |r, _| {
+                match *r {
+                    ty::ReStatic | ty::ReEarlyBound(_) => r,
+                    _ => tcx.types.re_erased,
+                }
+            });
+            let span = hir_id.to_span(&self.fcx.tcx);
+            sigs.push((span.lo(), span, fn_sig.to_string()));
+        }
+
+        sigs.sort_by_key(|&(lo, ..)| lo);
+        for (_, span, sig) in sigs {
+            tcx.sess.span_note_without_error(span, &format!("closure signature: `{}`", sig));
+        }
+    }
+
+    // Emits the `HirId`, kind, span and resolved type of every node of the
+    // body that has a type, in `HirId` order, for `-Z writeback-emit-spans`.
+    fn report_node_spans(&self) {
+        let tcx = self.tcx();
+        let owner = self.tables.local_id_root.unwrap().index;
+
+        // `hir::Arg` nodes are not in the HIR map, so find their spans from
+        // the arguments of the body and of the closures in it.
+        let mut arg_spans: Vec<_> = self.body.arguments.iter()
+            .map(|arg| (arg.hir_id, arg.pat.span))
+            .collect();
+        for (&local_id, _) in self.tables.closure_tys().iter() {
+            let hir_id = hir::HirId { owner, local_id };
+            let node_id = tcx.hir.definitions().find_node_for_hir_id(hir_id);
+            if let hir::ExprClosure(_, _, body_id, _, _) = tcx.hir.expect_expr(node_id).node {
+                arg_spans.extend(tcx.hir.body(body_id).arguments.iter()
+                                    .map(|arg| (arg.hir_id, arg.pat.span)));
+            }
+        }
+
+        let mut nodes: Vec<_> = self.tables.node_types().iter()
+            .map(|(&local_id, &ty)| (local_id, ty))
+            .collect();
+        nodes.sort_by_key(|&(local_id, _)| local_id);
+
+        for (local_id, ty) in nodes {
+            let hir_id = hir::HirId { owner, local_id };
+            let node_id = tcx.hir.definitions().find_node_for_hir_id(hir_id);
+            let (kind, span) = match tcx.hir.find(node_id) {
+                Some(hir_map::NodeExpr(expr)) => ("expr", expr.span),
+                Some(hir_map::NodeBinding(pat)) |
+                Some(hir_map::NodePat(pat)) => ("pat", pat.span),
+                Some(hir_map::NodeLocal(local)) => ("local", local.span),
+                Some(hir_map::NodeTy(ty)) => ("ty", ty.span),
+                Some(hir_map::NodeBlock(block)) => ("block", block.span),
+                Some(node) => span_bug!(tcx.hir.span(node_id),
+                                        "unexpected node with a type: {:?}", node),
+                None => {
+                    match arg_spans.iter().find(|&&(arg_id, _)| arg_id == hir_id) {
+                        Some(&(_, span)) => ("arg", span),
+                        None => bug!("node {:?} with a type is not in the HIR map", hir_id),
+                    }
+                }
+            };
+            tcx.sess.span_note_without_error(
+                span,
+                &format!("writeback-emit-spans: {}:{} {} `{}`",
+                         owner.as_usize(), local_id.as_usize(), kind, ty));
+        }
+    }
+
+    // Emits, at the span each inference variable was created for, the type
+    // it was resolved to, in the order the variables were created, for
+    // `-Z dump-infer-vars`. Only variables that appear in the types
+    // writeback resolved are included.
+    fn report_infer_vars(&self) {
+        let infer_vars = self.infer_vars.borrow();
+        let mut vids: Vec<_> = infer_vars.keys().cloned().collect();
+        vids.sort_by_key(|vid| vid.index);
+
+        let type_variables = self.fcx.type_variables.borrow();
+        for vid in vids {
+            let span = type_variables.var_origin(vid).span().unwrap_or(self.body.value.span);
+            self.tcx().sess.span_note_without_error(
+                span,
+                &format!("dump-infer-vars: `{:?}` resolved to `{}`", vid, infer_vars[&vid]));
+        }
+    }
+
+    // Emits, for each node whose type is not fully inferred, the type it
+    // would have if the first type variable left unresolved in the body
+    // were the primitive type `ty_name`, for `-Z resolve-ambiguous-as`. The
+    // real resolution of the body is not affected.
+    fn report_counterfactual_tys(&self, ty_name: &str) {
+        let tcx = self.tcx();
+        let pinned_ty = match primitive_ty_named(tcx, ty_name) {
+            Some(ty) => ty,
+            None => {
+                tcx.sess.err(&format!("`-Z resolve-ambiguous-as`: `{}` is not a primitive type",
+                                      ty_name));
+                return;
+            }
+        };
+
+        let owner = self.tables.local_id_root.unwrap().index;
+        let fcx_tables = self.fcx.tables.borrow();
+        let mut nodes: Vec<_> = fcx_tables.node_types().iter()
+            .map(|(&local_id, &ty)| (local_id, self.fcx.resolve_type_vars_if_possible(&ty)))
+            .filter(|&(local_id, ty)| {
+                // `hir::Arg` nodes are not in the HIR map, but their
+                // patterns are, with the same type.
+                let hir_id = hir::HirId { owner, local_id };
+                let node_id = tcx.hir.definitions().find_node_for_hir_id(hir_id);
+                ty.has_infer_types() && tcx.hir.find(node_id).is_some()
+            })
+            .collect();
+        nodes.sort_by_key(|&(local_id, _)| local_id);
+
+        let vid = nodes.iter().flat_map(|&(_, ty)| ty.walk()).filter_map(|ty| {
+            match ty.sty {
+                ty::TyInfer(ty::TyVar(vid)) => Some(vid),
+                _ => None,
+            }
+        }).next();
+        let vid = match vid {
+            Some(vid) => vid,
+            None => return,
+        };
+
+        let origin_span = self.fcx.type_variables.borrow().var_origin(vid).span()
+                                                   .unwrap_or(self.body.value.span);
+        let consistent = self.fcx.probe_as_if(vid, pinned_ty, self.fcx.param_env, || {
+            for &(local_id, ty) in &nodes {
+                let hir_id = hir::HirId { owner, local_id };
+                let msg = match self.fcx.fully_resolve(&ty) {
+                    Ok(ty) => format!("resolve-ambiguous-as: would have type `{}`", ty),
+                    Err(_) => "resolve-ambiguous-as: would still not be inferred".to_string(),
+                };
+                tcx.sess.span_note_without_error(hir_id.to_span(&self.fcx.tcx), &msg);
+            }
+        });
+        let msg = match consistent {
+            Some(()) => format!("resolve-ambiguous-as: assuming this type is `{}`", pinned_ty),
+            None => format!("resolve-ambiguous-as: this type cannot be `{}`", pinned_ty),
+        };
+        tcx.sess.span_note_without_error(origin_span, &msg);
+    }
+
+    // Emits, for each qualified path type written in the body that does not
+    // resolve, like `<T as Trait>::Assoc` without `T: Trait`, the bound under
+    // which it would, for `-Z assumed-bound-hints`. The error for the type
+    // is reported as usual.
+    fn report_assumed_bound_hints(&self) {
+        let tcx = self.tcx().global_tcx();
+        let owner = self.tables.local_id_root.unwrap().index;
+        let mut tys: Vec<_> = self.tables.unnormalized_tys().iter()
+            .map(|(&local_id, &ty)| (local_id, ty))
+            .collect();
+        tys.sort_by_key(|&(local_id, _)| local_id);
+
+        for (local_id, ty) in tys {
+            if ty.references_error() || self.resolve_assuming(ty, None).is_some() {
+                continue;
+            }
+
+            let assumed_bounds = ty.walk().filter_map(|t| match t.sty {
+                ty::TyProjection(ref data) => Some(data.trait_ref(tcx).to_predicate()),
+                _ => None,
+            });
+            for assumed in assumed_bounds {
+                if let Some(resolved) = self.resolve_assuming(ty, Some(assumed)) {
+                    let hir_id = hir::HirId { owner, local_id };
+                    tcx.sess.span_note_without_error(
+                        hir_id.to_span(&self.fcx.tcx),
+                        &format!("adding the bound `{}` would let this type resolve to `{}`",
+                                 assumed,
+                                 resolved));
+                    break;
+                }
+            }
+        }
+    }
+
+    // Normalizes `ty`, which `resolve` already resolved, under the
+    // param-env of the body extended with the `assumed` predicate. This
+    // happens in a separate inference context, so the real resolution of
+    // the body is not affected. Returns `None` if `ty` does not resolve
+    // even then.
+    fn resolve_assuming(&self, ty: Ty<'gcx>, assumed: Option<ty::Predicate<'gcx>>)
+                        -> Option<Ty<'gcx>> {
+        let tcx = self.tcx().global_tcx();
+        let param_env = self.param_env();
+        let param_env = match assumed {
+            Some(assumed) => {
+                let mut predicates = param_env.caller_bounds.to_vec();
+                predicates.push(assumed);
+                ty::ParamEnv::new(tcx.intern_predicates(&predicates), param_env.reveal)
+            }
+            None => param_env,
+        };
+
+        tcx.infer_ctxt().enter(|infcx| {
+            let cause = traits::ObligationCause::dummy();
+            traits::fully_normalize(&infcx, cause, param_env, &ty).ok()
+                .and_then(|ty| infcx.tcx.lift_to_global(&ty))
+        })
+    }
+
+    // `check_fn` writes the type of each argument from the inputs of the
+    // liberated signature, so once both are resolved they must still agree.
+    // This compares the `hir::Arg` nodes rather than their patterns, as the
+    // pattern of a `ref x` argument has the type of the reference.
+    #[cfg(debug_assertions)]
+    fn audit_arg_types(&self, item_id: ast::NodeId) {
+        if self.tables.tainted_by_errors {
+            return;
+        }
+
+        let fn_hir_id = self.tcx().hir.node_to_hir_id(item_id);
+        let fn_sig = match self.tables.liberated_fn_sigs().get(fn_hir_id) {
+            Some(fn_sig) => fn_sig.clone(),
+            None => return,
+        };
+
+        for (&input_ty, arg) in fn_sig.inputs().iter().zip(&self.body.arguments) {
+            let arg_ty = self.tables.node_id_to_type(arg.hir_id);
+            if arg_ty != input_ty {
+                span_bug!(arg.pat.span,
+                          "argument has resolved type `{:?}`, but the liberated signature \
+                           has `{:?}`",
+                          arg_ty,
+                          input_ty);
+            }
+        }
+    }
+
+    // A function such as `fn f() -> impl Fn() { || {} }` registers both an
+    // anon type for its return type and a type for the closure, and the
+    // hidden type written back for the former must be the latter. Regions
+    // are erased, as `visit_anon_types` replaces them in the hidden type.
+    #[cfg(debug_assertions)]
+    fn audit_closure_anon_type(&self, item_id: ast::NodeId) {
+        if self.tables.tainted_by_errors {
+            return;
+        }
+
+        let fn_hir_id = self.tcx().hir.node_to_hir_id(item_id);
+        let anon_def_id = match self.tables.liberated_fn_sigs().get(fn_hir_id) {
+            Some(fn_sig) => match fn_sig.output().sty {
+                ty::TyAnon(def_id, _) => def_id,
+                _ => return,
+            },
+            None => return,
+        };
+
+        let closure_expr = match self.body.value.node {
+            hir::ExprBlock(ref block) => match block.expr {
+                Some(ref expr) => match expr.node {
+                    hir::ExprClosure(..) => expr,
+                    _ => return,
+                },
+                None => return,
+            },
+            _ => return,
+        };
+
+        let anon_node_id = self.tcx().hir.as_local_node_id(anon_def_id).unwrap();
+        let anon_hir_id = self.tcx().hir.node_to_hir_id(anon_node_id);
+        let hidden_ty = self.tables.node_id_to_type(anon_hir_id);
+        let closure_ty = self.tables.expr_ty(closure_expr);
+        if self.tcx().erase_regions(&hidden_ty) != self.tcx().erase_regions(&closure_ty) {
+            span_bug!(closure_expr.span,
+                      "returned closure has resolved type `{:?}`, but the hidden type \
+                       of the `impl Trait` is `{:?}`",
+                      closure_ty,
+                      hidden_ty);
+        }
+    }
+
+    // `check_cast` classifies each cast before its types are fully
+    // resolved, so check that the recorded kind still describes the
+    // resolved operand and target types. The operand type is taken after
+    // adjustments, which is where a function item is reified for a cast.
+    #[cfg(debug_assertions)]
+    fn audit_cast_kinds(&self) {
+        use rustc::ty::cast::{CastKind, CastTy, IntTy};
+
+        if self.tables.tainted_by_errors {
+            return;
+        }
+
+        let owner = self.tables.local_id_root.unwrap();
+        for (&local_id, &cast_kind) in self.tables.cast_kinds().iter() {
+            let hir_id = hir::HirId { owner: owner.index, local_id };
+            let node_id = self.tcx().hir.definitions().find_node_for_hir_id(hir_id);
+            let expr = self.tcx().hir.expect_expr(node_id);
+            let operand = match expr.node {
+                hir::ExprCast(ref operand, _) => operand,
+                _ => span_bug!(expr.span, "cast kind `{:?}` recorded for a non-cast", cast_kind),
+            };
+
+            let from_ty = self.tables.expr_ty_adjusted(operand);
+            let to_ty = self.tables.expr_ty(expr);
+            let consistent = match (cast_kind, CastTy::from_ty(from_ty), CastTy::from_ty(to_ty)) {
+                (CastKind::CoercionCast, ..) => true,
+                (CastKind::NumericCast, Some(CastTy::Int(_)), Some(CastTy::Int(_))) |
+                (CastKind::NumericCast, Some(CastTy::Int(_)), Some(CastTy::Float)) |
+                (CastKind::NumericCast, Some(CastTy::Float), Some(CastTy::Int(_))) |
+                (CastKind::NumericCast, Some(CastTy::Float), Some(CastTy::Float)) => true,
+                (CastKind::EnumCast, Some(CastTy::Int(IntTy::CEnum)), Some(CastTy::Int(_))) => {
+                    true
+                }
+                (CastKind::PrimIntCast, Some(CastTy::Int(IntTy::Bool)), Some(CastTy::Int(_))) |
+                (CastKind::PrimIntCast, Some(CastTy::Int(IntTy::Char)), Some(CastTy::Int(_))) => {
+                    true
+                }
+                (CastKind::U8CharCast,
+                 Some(CastTy::Int(IntTy::U(ast::UintTy::U8))),
+                 Some(CastTy::Int(IntTy::Char))) => true,
+                (CastKind::PtrPtrCast, Some(CastTy::Ptr(_)), Some(CastTy::Ptr(_))) |
+                (CastKind::PtrAddrCast, Some(CastTy::Ptr(_)), Some(CastTy::Int(_))) |
+                (CastKind::AddrPtrCast, Some(CastTy::Int(_)), Some(CastTy::Ptr(_))) |
+                (CastKind::FnPtrPtrCast, Some(CastTy::FnPtr), Some(CastTy::Ptr(_))) |
+                (CastKind::FnPtrAddrCast, Some(CastTy::FnPtr), Some(CastTy::Int(_))) => true,
+                (CastKind::ArrayPtrCast, Some(CastTy::RPtr(mt)), Some(CastTy::Ptr(_))) => {
+                    match mt.ty.sty {
+                        ty::TyArray(..) => true,
+                        _ => false,
+                    }
+                }
+                _ => false,
+            };
+
+            if !consistent {
+                span_bug!(expr.span,
+                          "cast kind `{:?}` is inconsistent with the resolved cast \
+                           from `{:?}` to `{:?}`",
+                          cast_kind,
+                          from_ty,
+                          to_ty);
+            }
+        }
+    }
+
+    // A closure kind stronger than `Fn` is inferred from a use of one of the
+    // closure's upvars, which is recorded as the origin of the kind. That
+    // upvar must be captured in a way that needs the kind: by value for
+    // `FnOnce`, and by mutable or unique borrow for `FnMut`, or by value if
+    // the closure is `move`. There is nothing to check for kinds taken from
+    // the expected type, which have no origin, as a use they do not permit
+    // is an error reported by borrowck.
+    #[cfg(debug_assertions)]
+    fn audit_closure_kinds(&self) {
+        if self.tables.tainted_by_errors {
+            return;
+        }
+
+        let tcx = self.tcx();
+        let owner = self.tables.local_id_root.unwrap();
+        for (&local_id, &(kind, origin)) in self.tables.closure_kinds().iter() {
+            let var_name = match origin {
+                Some((_, var_name)) => var_name,
+                None => continue,
+            };
+            let closure_hir_id = hir::HirId { owner: owner.index, local_id };
+            let closure_node_id = tcx.hir.definitions().find_node_for_hir_id(closure_hir_id);
+            let captures = self.tables.closure_captures(tcx.hir.local_def_id(closure_node_id));
+
+            let consistent = captures.iter().any(|&(var_id, capture)| {
+                let var_node_id = tcx.hir.definitions().find_node_for_hir_id(var_id);
+                tcx.hir.name(var_node_id) == var_name && match (kind, capture) {
+                    (ty::ClosureKind::FnOnce, ty::UpvarCapture::ByValue) |
+                    (ty::ClosureKind::FnMut, ty::UpvarCapture::ByValue) => true,
+                    (ty::ClosureKind::FnMut, ty::UpvarCapture::ByRef(borrow)) => {
+                        borrow.kind != ty::ImmBorrow
+                    }
+                    _ => false,
+                }
+            });
+            if !consistent {
+                span_bug!(closure_hir_id.to_span(&self.fcx.tcx),
+                          "closure is `{:?}` because of `{}`, but its captures are {:?}",
+                          kind,
+                          var_name,
+                          captures);
+            }
+        }
+    }
+
+    // `check_expr_struct` records the normalized type of every field of a
+    // struct built with `..base`. Once resolved, they must be the declared
+    // field types under the substs of the struct expression's type.
+    #[cfg(debug_assertions)]
+    fn audit_fru_field_types(&self) {
+        if self.tables.tainted_by_errors {
+            return;
+        }
+
+        let tcx = self.tcx().global_tcx();
+        let param_env = self.param_env();
+        let owner = self.tables.local_id_root.unwrap();
+        for (&local_id, ftys) in self.tables.fru_field_types().iter() {
+            let hir_id = hir::HirId { owner: owner.index, local_id };
+            let span = hir_id.to_span(&self.fcx.tcx);
+            let (adt, substs) = match self.tables.node_id_to_type(hir_id).sty {
+                ty::TyAdt(adt, substs) if adt.is_struct() => (adt, substs),
+                ref sty => span_bug!(span, "functional record update of `{:?}`", sty),
+            };
+
+            let fields = &adt.struct_variant().fields;
+            if fields.len() != ftys.len() {
+                span_bug!(span,
+                          "functional record update has {} field types, but `{}` has {} fields",
+                          ftys.len(),
+                          tcx.item_path_str(adt.did),
+                          fields.len());
+            }
+            for (field, &fty) in fields.iter().zip(ftys) {
+                let declared_ty = tcx.normalize_associated_type_in_env(&field.ty(tcx, substs),
+                                                                       param_env);
+                if tcx.erase_regions(&fty) != declared_ty {
+                    span_bug!(span,
+                              "functional record update has type `{}` for field `{}`, \
+                               which is declared as `{}`",
+                              fty,
+                              field.name,
+                              declared_ty);
+                }
+            }
+        }
+    }
+
+    // Borrowck relates the regions in `node_substs`, so none of them may be
+    // erased before it runs, unlike the regions of the candidates seen
+    // during method probing.
+    #[cfg(debug_assertions)]
+    fn audit_node_substs_regions(&self) {
+        if self.tables.tainted_by_errors {
+            return;
+        }
+
+        let tcx = self.tcx().global_tcx();
+        let owner = self.tables.local_id_root.unwrap();
+        for (&local_id, _) in self.tables.node_types().iter() {
+            let hir_id = hir::HirId { owner: owner.index, local_id };
+            let substs = match self.tables.node_substs_opt(hir_id) {
+                Some(substs) => substs,
+                None => continue,
+            };
+            let mut has_erased = false;
+            tcx.fold_regions(&substs, &mut false, |r, _| {
+                if let ty::ReErased = *r {
+                    has_erased = true;
+                }
+                r
+            });
+            if has_erased {
+                span_bug!(hir_id.to_span(&self.fcx.tcx),
+                          "node {:?} has erased regions in its substs `{:?}`",
+                          hir_id,
+                          substs);
+            }
+        }
+    }
+
+    // Checks that no inference variable escaped into the tables built by
+    // writeback. Node types are not checked again, as `write_ty_to_tables`
+    // already asserts it as it writes them.
+    fn verify_no_infer_vars(&self) {
+        let owner = self.tables.local_id_root.unwrap();
+        for (&local_id, _) in self.tables.node_types().iter() {
+            let hir_id = hir::HirId { owner: owner.index, local_id };
+            if let Some(substs) = self.tables.node_substs_opt(hir_id) {
+                self.verify_no_infer_vars_in("node substs", local_id, &substs);
+            }
+        }
+        for (&local_id, adjustments) in self.tables.adjustments().iter() {
+            self.verify_no_infer_vars_in("adjustments", local_id, adjustments);
+        }
+        for (&local_id, tys) in self.tables.pat_adjustments().iter() {
+            self.verify_no_infer_vars_in("pattern adjustments", local_id, tys);
+        }
+        for (&local_id, sig) in self.tables.closure_tys().iter() {
+            self.verify_no_infer_vars_in("closure signature", local_id, sig);
+        }
+        for (&local_id, sig) in self.tables.liberated_fn_sigs().iter() {
+            self.verify_no_infer_vars_in("liberated fn signature", local_id, sig);
+        }
+        for (&local_id, tys) in self.tables.fru_field_types().iter() {
+            self.verify_no_infer_vars_in("FRU field types", local_id, tys);
+        }
+        for (&local_id, sig) in self.tables.generator_sigs().iter() {
+            self.verify_no_infer_vars_in("generator signature", local_id, sig);
+        }
+        for (&local_id, interior) in self.tables.generator_interiors().iter() {
+            self.verify_no_infer_vars_in("generator interior", local_id, interior);
+        }
+        for (&local_id, &ty) in self.tables.unnormalized_tys().iter() {
+            self.verify_no_infer_vars_in("unnormalized type", local_id, &ty);
+        }
+    }
+
+    fn verify_no_infer_vars_in<T>(&self, table: &str, local_id: hir::ItemLocalId, value: &T)
+        where T: TypeFoldable<'gcx> + fmt::Debug
+    {
+        if value.needs_infer() {
+            let owner = self.tables.local_id_root.unwrap();
+            let hir_id = hir::HirId { owner: owner.index, local_id };
+            span_bug!(hir_id.to_span(&self.fcx.tcx),
+                      "node {:?} has inference variables in its {} after writeback: `{:?}`",
+                      hir_id,
+                      table,
+                      value);
+        }
+    }
+
+    // A binding written without `ref`, `ref mut` or `mut` gets the default
+    // binding mode, which comes from the references that the enclosing
+    // patterns matched through, as recorded in their pattern adjustments.
+    // This recomputes the default binding mode of `p` from its resolved
+    // adjustments the way `check_pat_walk` does, checks the mode recorded
+    // for `p` if it is a binding, and returns the default binding mode of
+    // its subpatterns.
+    #[cfg(debug_assertions)]
+    fn audit_pat_binding_mode(&self, p: &hir::Pat) -> ty::BindingMode {
+        let mut def_bm = self.pat_default_bms.last().cloned().unwrap_or(
+            ty::BindingMode::BindByValue(hir::Mutability::MutImmutable));
+
+        if let Some(adjustments) = self.tables.pat_adjustments().get(p.hir_id) {
+            for ref_ty in adjustments {
+                let mutbl = match ref_ty.sty {
+                    ty::TyRef(_, mt) => mt.mutbl,
+                    _ => span_bug!(p.span, "pattern adjusted through non-reference `{:?}`", ref_ty),
+                };
+                def_bm = match def_bm {
+                    ty::BindByValue(_) |
+                    ty::BindByReference(hir::Mutability::MutMutable) => {
+                        ty::BindByReference(mutbl)
+                    }
+                    ty::BindByReference(hir::Mutability::MutImmutable) => {
+                        ty::BindByReference(hir::Mutability::MutImmutable)
+                    }
+                };
+            }
+        }
+
+        if let hir::PatKind::Binding(ba, ..) = p.node {
+            let expected = if ba == hir::BindingAnnotation::Unannotated {
+                def_bm
+            } else {
+                ty::BindingMode::convert(ba)
+            };
+            let recorded = self.tables.pat_binding_modes().get(p.hir_id).cloned();
+            if recorded != Some(expected) && !self.fcx.is_tainted_by_errors() {
+                span_bug!(p.span,
+                          "binding has mode {:?}, but the references matched by the \
+                           patterns around it give {:?}",
+                          recorded,
+                          expected);
+            }
+        }
+
+        def_bm
+    }
+
+    // The tail expression of an `unsafe` block is coerced to the type of the
+    // block, so once both are resolved, the adjusted type of the tail must be
+    // a subtype of the block's type. This runs as each block is visited, as
+    // the `hir::Block`s are not kept once writeback is done.
+    #[cfg(debug_assertions)]
+    fn audit_unsafe_block_ty(&self, b: &hir::Block) {
+        if self.fcx.is_tainted_by_errors() {
+            return;
+        }
+
+        let tail = match (b.rules, &b.expr) {
+            (hir::UnsafeBlock(_), &Some(ref tail)) => tail,
+            _ => return,
+        };
+        let block_ty = self.tables.node_id_to_type(b.hir_id);
+        let tail_ty = self.tables.expr_ty_adjusted(tail);
+        if self.fcx.can_sub(self.fcx.param_env, tail_ty, block_ty).is_err() {
+            span_bug!(b.span,
+                      "`unsafe` block has type `{}`, but its tail expression has type `{}`",
+                      block_ty,
+                      tail_ty);
+        }
+    }
+
+    // Pattern adjustments are only recorded for patterns that were checked,
+    // and so have a type of their own, which the adjusted patterns deref to.
+    #[cfg(debug_assertions)]
+    fn audit_pat_adjustments(&self) {
+        let owner = self.tables.local_id_root.unwrap();
+        for (&local_id, adjustments) in self.tables.pat_adjustments().iter() {
+            let hir_id = hir::HirId { owner: owner.index, local_id };
+            if self.tables.node_types().get(hir_id).is_none() {
+                span_bug!(hir_id.to_span(&self.fcx.tcx),
+                          "pattern has adjustments `{:?}` but no resolved type",
+                          adjustments);
+            }
+        }
+    }
+
     fn write_ty_to_tables(&mut self, hir_id: hir::HirId, ty: Ty<'gcx>) {
         debug!("write_ty_to_tables({:?}, {:?})", hir_id,  ty);
         assert!(!ty.needs_infer());
@@ -123,7 +876,20 @@ impl<'cx, 'gcx, 'tcx> WritebackCx<'cx, 'gcx, 'tcx> {
                 let rhs_ty = self.fcx.node_ty(rhs.hir_id);
                 let rhs_ty = self.fcx.resolve_type_vars_if_possible(&rhs_ty);
 
-                if lhs_ty.is_scalar() && rhs_ty.is_scalar() {
+                // The operands of a builtin shift are integers, but unlike
+                // other operators not necessarily of the same type. The
+                // right-hand side is still checked because of impls like
+                // `Shl<Foo> for u64`. If it is left unresolved, an error has
+                // been reported, and the shift is kept out of the overloaded
+                // operators rather than leaving a stale method behind.
+                let is_builtin = match op.node {
+                    hir::BiShl | hir::BiShr => {
+                        lhs_ty.is_integral() && (rhs_ty.is_integral() || rhs_ty.is_ty_var())
+                    }
+                    _ => lhs_ty.is_scalar() && rhs_ty.is_scalar(),
+                };
+
+                if is_builtin {
                     let mut tables = self.fcx.tables.borrow_mut();
                     tables.type_dependent_defs_mut().remove(e.hir_id);
                     tables.node_substs_mut().remove(e.hir_id);
@@ -146,6 +912,414 @@ impl<'cx, 'gcx, 'tcx> WritebackCx<'cx, 'gcx, 'tcx> {
             _ => {},
         }
     }
+
+    // Similarly, indexing an array or slice with an index whose type was
+    // not yet known to be `usize` is treated as overloaded, through the
+    // `Index` impl for slices. If it turns out to be builtin indexing,
+    // clear the method call and the autoref (and unsizing) that was
+    // applied to the base for it, leaving only the autoderefs.
+    fn fix_index_builtin_expr(&mut self, e: &hir::Expr) {
+        if let hir::ExprIndex(ref base, ref index) = e.node {
+            let mut tables = self.fcx.tables.borrow_mut();
+
+            let base_ty = tables.expr_ty_adjusted(base);
+            let base_ty = self.fcx.resolve_type_vars_if_possible(&base_ty);
+            let indexed_ty = match base_ty.sty {
+                ty::TyRef(_, mt) => mt.ty,
+                // Builtin indexing does not borrow its base.
+                _ => return,
+            };
+
+            let index_ty = tables.expr_ty_adjusted(index);
+            let index_ty = self.fcx.resolve_type_vars_if_possible(&index_ty);
+
+            if indexed_ty.builtin_index().is_some() && index_ty == self.fcx.tcx.types.usize {
+                tables.type_dependent_defs_mut().remove(e.hir_id);
+                tables.node_substs_mut().remove(e.hir_id);
+                tables.adjustments_mut().get_mut(base.hir_id).map(|a| {
+                    // The unsizing of an array to a slice comes after the
+                    // autoref, so pop both.
+                    if let Some(Adjustment { kind: Adjust::Unsize, .. }) = a.pop() {
+                        a.pop();
+                    }
+                });
+            }
+        }
+    }
+
+    // Records whether `e` denotes a place rather than a value. Overloaded
+    // derefs and indexing (`*x` and `a[i]` where the operand's type
+    // implements `Deref` or `Index`) still denote places, since they
+    // dereference the reference returned by the overloaded method.
+    fn visit_place_expr(&mut self, e: &hir::Expr) {
+        let is_place = match e.node {
+            hir::ExprUnary(hir::UnDeref, _) |
+            hir::ExprIndex(..) |
+            hir::ExprField(..) |
+            hir::ExprTupField(..) => true,
+            hir::ExprPath(ref qpath) => {
+                match self.tables.qpath_def(qpath, e.hir_id) {
+                    Def::Local(..) | Def::Upvar(..) | Def::Static(..) => true,
+                    _ => false,
+                }
+            }
+            _ => false,
+        };
+
+        if is_place {
+            self.tables.record_place_expr(e.hir_id);
+        }
+    }
+
+    // Records whether the borrow `e` reborrows through a reference. This
+    // runs once `e` has been walked, so that the resolved types and
+    // adjustments of the borrowed place are already in `self.tables`.
+    fn visit_reborrow(&mut self, e: &hir::Expr) {
+        let mut place = match e.node {
+            hir::ExprAddrOf(_, ref place) => place,
+            _ => return,
+        };
+
+        let is_reborrow = loop {
+            place = match place.node {
+                hir::ExprUnary(hir::UnDeref, ref base) if !self.tables.is_method_call(place) => {
+                    break self.tables.expr_ty_adjusted(base).is_region_ptr();
+                }
+                hir::ExprIndex(..) if self.tables.is_method_call(place) => break false,
+                hir::ExprField(ref base, _) |
+                hir::ExprTupField(ref base, _) |
+                hir::ExprIndex(ref base, _) => {
+                    if self.autoderefs_ref(base) {
+                        break true;
+                    }
+                    base
+                }
+                _ => break false,
+            };
+        };
+
+        if is_reborrow {
+            self.tables.record_reborrow(e.hir_id);
+        }
+    }
+
+    // Whether the adjustments of `e` include a builtin deref of a reference,
+    // like the autoderef of `r` in `r.field`.
+    fn autoderefs_ref(&self, e: &hir::Expr) -> bool {
+        let mut ty = self.tables.expr_ty(e);
+        for adjustment in self.tables.expr_adjustments(e) {
+            if let Adjust::Deref(None) = adjustment.kind {
+                if ty.is_region_ptr() {
+                    return true;
+                }
+            }
+            ty = adjustment.target;
+        }
+        false
+    }
+
+    // With `-Z precompute-sized`, records whether the resolved type of `e`
+    // is `Sized`.
+    fn visit_sized(&mut self, e: &hir::Expr) {
+        if !self.tcx().sess.opts.debugging_opts.precompute_sized {
+            return;
+        }
+
+        let ty = self.tables.expr_ty(e);
+        if !ty.references_error() {
+            let is_sized = ty.is_sized(self.tcx().global_tcx(), self.param_env(), e.span);
+            self.tables.sized_exprs_mut().insert(e.hir_id, is_sized);
+        }
+    }
+
+    // Records the family of the operand of the `?` expression `e`, which is
+    // lowered to `match Try::into_result(<operand>) { Err(err) => ..., Ok(val) => ... }`.
+    // The `Result` enum is taken from the desugared patterns, and `Option`
+    // is recognized by its path in libcore.
+    fn visit_try_kind(&mut self, e: &hir::Expr) {
+        let (discr, arms) = match e.node {
+            hir::ExprMatch(ref discr, ref arms, hir::MatchSource::TryDesugar) => (discr, arms),
+            _ => return,
+        };
+        let operand = match discr.node {
+            hir::ExprCall(_, ref args) if args.len() == 1 => &args[0],
+            _ => return,
+        };
+
+        let tcx = self.tcx();
+        let result_def_id = arms.iter().filter_map(|arm| match arm.pats[0].node {
+            hir::PatKind::TupleStruct(hir::QPath::Resolved(_, ref path), ..) => {
+                match path.def {
+                    Def::VariantCtor(variant_def_id, _) => tcx.parent_def_id(variant_def_id),
+                    _ => None,
+                }
+            }
+            _ => None,
+        }).next();
+
+        let kind = match (&self.tables.expr_ty(operand).sty, result_def_id) {
+            (&ty::TyAdt(adt, _), Some(result_def_id)) if adt.did == result_def_id => {
+                ty::TryKind::Result
+            }
+            (&ty::TyAdt(adt, _), _) if self.is_std_item(adt.did, "core::option::Option") => {
+                ty::TryKind::Option
+            }
+            _ => ty::TryKind::Other,
+        };
+        self.tables.try_kinds_mut().insert(e.hir_id, kind);
+    }
+
+    // Records the facts about `e` that lints want to check; see
+    // `ty::lint_hint`. This runs once `e` has been walked, so that the
+    // resolved types of its operands are already in `self.tables`.
+    fn visit_lint_hints(&mut self, e: &hir::Expr) {
+        let mut hints = vec![];
+
+        if let hir::ExprMethodCall(_, _, ref args) = e.node {
+            if self.is_clone_on_copy(e, &args[0]) {
+                hints.push(LintHint::CloneOnCopy);
+            }
+            if self.is_string_to_string(e, &args[0]) {
+                hints.push(LintHint::StringToString);
+            }
+        }
+
+        if let hir::ExprUnary(hir::UnDeref, ref operand) = e.node {
+            if self.is_deref_of_box_new(e, operand) {
+                hints.push(LintHint::DerefOfBoxNew);
+            }
+        }
+
+        if let hir::ExprBinary(op, ref lhs, ref rhs) = e.node {
+            if op.node.is_comparison() &&
+               self.autoderefs_operand(lhs) != self.autoderefs_operand(rhs) {
+                hints.push(LintHint::AsymmetricComparisonDeref);
+            }
+        }
+
+        if let hir::ExprLit(ref lit) = e.node {
+            if let ast::LitKind::Int(value, _) = lit.node {
+                if self.is_overflowing_int_lit(e, value) {
+                    hints.push(LintHint::OverflowingLiteral(value));
+                }
+            }
+        }
+
+        // The arguments are visited before the `match` that `format_args!`
+        // wraps them in, so their hints are added to any they already have.
+        for arg in self.format_args_arguments(e) {
+            if self.tables.expr_ty(arg).is_nil() {
+                self.tables.lint_hints_mut()
+                           .entry(arg.hir_id)
+                           .or_insert_with(Vec::new)
+                           .push(LintHint::FormatUnitArgument);
+            }
+        }
+
+        if !hints.is_empty() {
+            self.tables.lint_hints_mut().insert(e.hir_id, hints);
+        }
+    }
+
+    // The arguments of a `format_args!` expansion, if `e` is one. The
+    // expansion evaluates its arguments in a `match (&arg0, &arg1, ..)`
+    // whose only arm binds them to generated names. The argument
+    // expressions keep the spans they were written with, so the arm's
+    // pattern is what tells the expansion apart from a user's `match`.
+    fn format_args_arguments<'b>(&self, e: &'b hir::Expr) -> Vec<&'b hir::Expr> {
+        let (discr, arms) = match e.node {
+            hir::ExprMatch(ref discr, ref arms, hir::MatchSource::Normal) => (discr, arms),
+            _ => return vec![],
+        };
+        let heads = match discr.node {
+            hir::ExprTup(ref heads) => heads,
+            _ => return vec![],
+        };
+        if arms.len() != 1 || arms[0].pats.len() != 1 {
+            return vec![];
+        }
+        let pats = match arms[0].pats[0].node {
+            hir::PatKind::Tuple(ref pats, None) => pats,
+            _ => return vec![],
+        };
+        let from_format_args = |pat: &hir::Pat| {
+            match pat.span.ctxt().outer().expn_info() {
+                Some(info) => match info.callee.format {
+                    ExpnFormat::MacroBang(name) => name == "format_args",
+                    _ => false,
+                },
+                None => false,
+            }
+        };
+        if pats.is_empty() || pats.len() != heads.len() ||
+           !pats.iter().all(|pat| from_format_args(pat)) {
+            return vec![];
+        }
+
+        heads.iter().filter_map(|head| match head.node {
+            hir::ExprAddrOf(_, ref arg) => Some(&**arg),
+            _ => None,
+        }).collect()
+    }
+
+    fn is_clone_on_copy(&self, e: &hir::Expr, receiver: &hir::Expr) -> bool {
+        let tcx = self.tcx().global_tcx();
+
+        let method_def_id = match self.tables.type_dependent_defs().get(e.hir_id) {
+            Some(&Def::Method(def_id)) => def_id,
+            _ => return false,
+        };
+        if tcx.trait_of_item(method_def_id) != tcx.lang_items().clone_trait() ||
+           tcx.item_name(method_def_id) != "clone" {
+            return false;
+        }
+
+        // The receiver type before autoref, which is `T` for `x.clone()`
+        // with `x: T`. Generic receivers are `Copy` if the body's
+        // where-clauses say so.
+        let receiver_ty = self.tables.expr_ty(receiver);
+        !receiver_ty.references_error() &&
+            !receiver_ty.moves_by_default(tcx, self.param_env(), receiver.span)
+    }
+
+    fn is_string_to_string(&self, e: &hir::Expr, receiver: &hir::Expr) -> bool {
+        match self.tables.type_dependent_defs().get(e.hir_id) {
+            Some(&Def::Method(def_id))
+                if self.is_std_item(def_id, "alloc::string::ToString::to_string") => {}
+            _ => return false,
+        }
+
+        match self.tables.expr_ty(receiver).sty {
+            ty::TyAdt(adt_def, _) => self.is_std_item(adt_def.did, "alloc::string::String"),
+            _ => false,
+        }
+    }
+
+    // Whether `def_id` is the item of the standard library at `path`, which
+    // starts with the name of the crate that defines it. This is how items
+    // without a lang item, like `String` or `Option`, are recognized.
+    fn is_std_item(&self, def_id: DefId, path: &str) -> bool {
+        !def_id.is_local() && self.tcx().absolute_item_path_str(def_id) == path
+    }
+
+    // Whether `e` is `*operand`, with `operand` a call to `Box::new` whose
+    // argument has the type that the dereference produces.
+    fn is_deref_of_box_new(&self, e: &hir::Expr, operand: &hir::Expr) -> bool {
+        let tcx = self.tcx().global_tcx();
+
+        let (callee, arg) = match operand.node {
+            hir::ExprCall(ref callee, ref args) if args.len() == 1 => (callee, &args[0]),
+            _ => return false,
+        };
+        let fn_def_id = match self.tables.type_dependent_defs().get(callee.hir_id) {
+            Some(&Def::Method(def_id)) => def_id,
+            _ => return false,
+        };
+        let box_new_def_id = tcx.lang_items().owned_box().and_then(|box_def_id| {
+            tcx.inherent_impls(box_def_id).iter()
+                .flat_map(|&impl_def_id| tcx.associated_items(impl_def_id))
+                .find(|item| item.name == "new")
+                .map(|item| item.def_id)
+        });
+        if box_new_def_id != Some(fn_def_id) {
+            return false;
+        }
+
+        !self.tables.is_method_call(e) &&
+            self.tables.expr_ty(e) == self.tables.expr_ty_adjusted(arg)
+    }
+
+    // Whether the adjustments of the comparison operand `operand` start by
+    // dereferencing it. A single builtin deref is the reborrow of a `&mut`
+    // coerced to `&`, which borrows the same place again, so it does not
+    // count.
+    fn autoderefs_operand(&self, operand: &hir::Expr) -> bool {
+        let derefs: Vec<_> = self.tables.expr_adjustments(operand).iter().take_while(|adj| {
+            match adj.kind {
+                Adjust::Deref(_) => true,
+                _ => false,
+            }
+        }).collect();
+
+        match derefs.len() {
+            0 => false,
+            1 => match derefs[0].kind {
+                Adjust::Deref(None) => false,
+                _ => true,
+            },
+            _ => true,
+        }
+    }
+
+    // Whether the type annotation of `l` gives the type its initializer
+    // would have had anyway. To avoid false positives, only initializers
+    // that are literals used without coercion and whose type does not
+    // depend on the context are considered. Unsuffixed numbers are not:
+    // without the annotation their type is an integer or float variable,
+    // on which method calls like `x.pow(2)` are ambiguous, even when it
+    // would fall back to the annotated `i32` or `f64`.
+    fn is_redundant_annotation(&self, l: &hir::Local, var_ty: Ty<'gcx>) -> bool {
+        let init = match (&l.ty, &l.init) {
+            (&Some(ref ty), &Some(ref init)) if ty.node != hir::TyInfer => init,
+            _ => return false,
+        };
+        if !self.tables.expr_adjustments(init).is_empty() {
+            return false;
+        }
+
+        let tcx = self.tcx().global_tcx();
+        let lit_ty = match init.node {
+            hir::ExprLit(ref lit) => {
+                match lit.node {
+                    ast::LitKind::Int(_, ast::LitIntType::Signed(t)) => tcx.mk_mach_int(t),
+                    ast::LitKind::Int(_, ast::LitIntType::Unsigned(t)) => tcx.mk_mach_uint(t),
+                    ast::LitKind::Float(_, t) => tcx.mk_mach_float(t),
+                    ast::LitKind::Bool(_) => tcx.types.bool,
+                    ast::LitKind::Char(_) => tcx.types.char,
+                    ast::LitKind::Str(..) => tcx.mk_static_str(),
+                    _ => return false,
+                }
+            }
+            _ => return false,
+        };
+        // `let s: &str = "..";` gives `s` an inferred region, not 'static.
+        tcx.erase_regions(&lit_ty) == tcx.erase_regions(&var_ty)
+    }
+
+    // Both suffixed literals, like `300u8`, and literals whose type was
+    // inferred, as in `let x: u8 = 300;`, are checked against their
+    // resolved type. `isize` and `usize` depend on the target and are left
+    // to the `overflowing_literals` lint.
+    fn is_overflowing_int_lit(&self, e: &hir::Expr, value: u128) -> bool {
+        let max = match self.tables.expr_ty(e).sty {
+            ty::TyInt(t) => {
+                let bits = match t.bit_width() {
+                    Some(bits) => bits,
+                    None => return false,
+                };
+                let max = u128::max_value() >> (129 - bits);
+                if self.is_negated(e) { max + 1 } else { max }
+            }
+            ty::TyUint(t) => {
+                match t.bit_width() {
+                    Some(bits) => u128::max_value() >> (128 - bits),
+                    None => return false,
+                }
+            }
+            _ => return false,
+        };
+        value > max
+    }
+
+    fn is_negated(&self, e: &hir::Expr) -> bool {
+        let parent = self.tcx().hir.get_parent_node(e.id);
+        match self.tcx().hir.find(parent) {
+            Some(hir_map::NodeExpr(&hir::Expr { node: hir::ExprUnary(hir::UnNeg, _), .. })) => {
+                true
+            }
+            _ => false,
+        }
+    }
 }
 
 ///////////////////////////////////////////////////////////////////////////
@@ -163,8 +1337,10 @@ impl<'cx, 'gcx, 'tcx> Visitor<'gcx> for WritebackCx<'cx, 'gcx, 'tcx> {
 
     fn visit_expr(&mut self, e: &'gcx hir::Expr) {
         self.fix_scalar_builtin_expr(e);
+        self.fix_index_builtin_expr(e);
 
         self.visit_node_id(e.span, e.hir_id);
+        self.visit_place_expr(e);
 
         if let hir::ExprClosure(_, _, body, _, _) = e.node {
             let body = self.fcx.tcx.hir.body(body);
@@ -176,11 +1352,19 @@ impl<'cx, 'gcx, 'tcx> Visitor<'gcx> for WritebackCx<'cx, 'gcx, 'tcx> {
         }
 
         intravisit::walk_expr(self, e);
+
+        self.visit_reborrow(e);
+        self.visit_try_kind(e);
+        self.visit_sized(e);
+        self.visit_lint_hints(e);
     }
 
     fn visit_block(&mut self, b: &'gcx hir::Block) {
         self.visit_node_id(b.span, b.hir_id);
         intravisit::walk_block(self, b);
+
+        #[cfg(debug_assertions)]
+        self.audit_unsafe_block_ty(b);
     }
 
     fn visit_pat(&mut self, p: &'gcx hir::Pat) {
@@ -200,7 +1384,17 @@ impl<'cx, 'gcx, 'tcx> Visitor<'gcx> for WritebackCx<'cx, 'gcx, 'tcx> {
         self.visit_pat_adjustments(p.span, p.hir_id);
 
         self.visit_node_id(p.span, p.hir_id);
+
+        #[cfg(debug_assertions)]
+        {
+            let def_bm = self.audit_pat_binding_mode(p);
+            self.pat_default_bms.push(def_bm);
+        }
+
         intravisit::walk_pat(self, p);
+
+        #[cfg(debug_assertions)]
+        self.pat_default_bms.pop();
     }
 
     fn visit_local(&mut self, l: &'gcx hir::Local) {
@@ -208,6 +1402,10 @@ impl<'cx, 'gcx, 'tcx> Visitor<'gcx> for WritebackCx<'cx, 'gcx, 'tcx> {
         let var_ty = self.fcx.local_ty(l.span, l.id);
         let var_ty = self.resolve(&var_ty, &l.span);
         self.write_ty_to_tables(l.hir_id, var_ty);
+
+        if self.is_redundant_annotation(l, var_ty) {
+            self.tables.lint_hints_mut().insert(l.hir_id, vec![LintHint::RedundantTypeAnnotation]);
+        }
     }
 
     fn visit_ty(&mut self, hir_ty: &'gcx hir::Ty) {
@@ -219,6 +1417,25 @@ impl<'cx, 'gcx, 'tcx> Visitor<'gcx> for WritebackCx<'cx, 'gcx, 'tcx> {
 }
 
 impl<'cx, 'gcx, 'tcx> WritebackCx<'cx, 'gcx, 'tcx> {
+    // A condition that is not a `bool`, but whose type was only partly
+    // inferred when it was checked, like `Option<_>`, is reported here with
+    // the type it was finally inferred to have. This runs before anything is
+    // resolved, so that the mismatch is reported rather than the "type
+    // annotations needed" error it may cause.
+    fn report_condition_mismatches(&self) {
+        let mismatches = mem::replace(&mut *self.fcx.deferred_condition_mismatches.borrow_mut(),
+                                      vec![]);
+        for (ty, diagnostic) in mismatches {
+            let mut err = DiagnosticBuilder::new_diagnostic(self.tcx().sess.diagnostic(),
+                                                            diagnostic);
+            let cond_ty = self.fcx.resolve_type_vars_if_possible(&ty);
+            if !cond_ty.needs_infer() && !cond_ty.references_error() {
+                err.note(&format!("the condition was inferred to have type `{}`", cond_ty));
+            }
+            err.emit();
+        }
+    }
+
     fn visit_upvar_borrow_map(&mut self) {
         for (upvar_id, upvar_capture) in self.fcx.tables.borrow().upvar_capture_map.iter() {
             let new_upvar_capture = match *upvar_capture {
@@ -276,6 +1493,21 @@ impl<'cx, 'gcx, 'tcx> WritebackCx<'cx, 'gcx, 'tcx> {
         }
     }
 
+    fn visit_unnormalized_tys(&mut self) {
+        let fcx_tables = self.fcx.tables.borrow();
+        debug_assert_eq!(fcx_tables.local_id_root, self.tables.local_id_root);
+        let common_local_id_root = fcx_tables.local_id_root.unwrap();
+
+        for (&local_id, ty) in fcx_tables.unnormalized_tys().iter() {
+            let hir_id = hir::HirId {
+                owner: common_local_id_root.index,
+                local_id,
+            };
+            let ty = self.resolve(ty, &hir_id);
+            self.tables.unnormalized_tys_mut().insert(hir_id, ty);
+        }
+    }
+
     fn visit_free_region_map(&mut self) {
         let free_region_map = self.tcx().lift_to_global(&self.fcx.tables.borrow().free_region_map);
         let free_region_map = free_region_map.expect("all regions in free-region-map are global");
@@ -284,12 +1516,15 @@ impl<'cx, 'gcx, 'tcx> WritebackCx<'cx, 'gcx, 'tcx> {
 
     fn visit_anon_types(&mut self) {
         let gcx = self.tcx().global_tcx();
+        let body_owner_def_id = self.tcx().hir.body_owner_def_id(self.body.id());
+        let region_scope_tree = gcx.region_scope_tree(body_owner_def_id);
         for (&node_id, &concrete_ty) in self.fcx.anon_types.borrow().iter() {
             let inside_ty = self.resolve(&concrete_ty, &node_id);
 
             // Convert the type from the function into a type valid outside
             // the function, by replacing invalid regions with 'static,
-            // after producing an error for each of them.
+            // after producing an error listing all of them.
+            let mut invalid_regions = vec![];
             let outside_ty = gcx.fold_regions(&inside_ty, &mut false, |r, _| {
                 match *r {
                     // 'static and early-bound regions are valid.
@@ -301,10 +1536,9 @@ impl<'cx, 'gcx, 'tcx> WritebackCx<'cx, 'gcx, 'tcx> {
                     ty::ReLateBound(..) |
                     ty::ReScope(_) |
                     ty::ReSkolemized(..) => {
-                        let span = node_id.to_span(&self.fcx.tcx);
-                        span_err!(self.tcx().sess, span, E0564,
-                                  "only named lifetimes are allowed in `impl Trait`, \
-                                   but `{}` was found in the type `{}`", r, inside_ty);
+                        if !invalid_regions.contains(&r) {
+                            invalid_regions.push(r);
+                        }
                         gcx.types.re_static
                     }
 
@@ -316,6 +1550,27 @@ impl<'cx, 'gcx, 'tcx> WritebackCx<'cx, 'gcx, 'tcx> {
                 }
             });
 
+            if !invalid_regions.is_empty() {
+                let span = node_id.to_span(&self.fcx.tcx);
+                let found = invalid_regions.iter()
+                                           .map(|r| format!("`{}`", r))
+                                           .collect::<Vec<_>>()
+                                           .join(", ");
+                let verb = if invalid_regions.len() == 1 { "was" } else { "were" };
+                let mut err = struct_span_err!(self.tcx().sess, span, E0564,
+                                               "only named lifetimes are allowed in \
+                                                `impl Trait`, but {} {} found in the type `{}`",
+                                               found, verb, inside_ty);
+                // Point at where each offending region comes from, e.g.
+                // the block owning a local borrowed by a closure.
+                for &r in &invalid_regions {
+                    gcx.note_and_explain_region(&region_scope_tree, &mut err,
+                                                "the lifetime in the hidden type is \
+                                                 only valid for ", r, "");
+                }
+                err.emit();
+            }
+
             let hir_id = self.tcx().hir.node_to_hir_id(node_id);
             self.tables.node_types_mut().insert(hir_id, outside_ty);
         }
@@ -347,6 +1602,35 @@ impl<'cx, 'gcx, 'tcx> WritebackCx<'cx, 'gcx, 'tcx> {
             assert!(!substs.needs_infer());
             self.tables.node_substs_mut().insert(hir_id, substs);
         }
+
+        if self.tcx().sess.opts.debugging_opts.dump_mono_calls {
+            self.report_mono_call(span, hir_id, n_ty);
+        }
+    }
+
+    // Emits the callee and the type substs of `hir_id` if it is a method
+    // call, an overloaded operator or a reference to a fn item, for
+    // `-Z dump-mono-calls`. The node's type is checked last, as a method
+    // may return a fn item.
+    fn report_mono_call(&self, span: Span, hir_id: hir::HirId, n_ty: Ty<'gcx>) {
+        let method = match self.tables.type_dependent_defs().get(hir_id) {
+            Some(&Def::Method(def_id)) => {
+                self.tables.node_substs_opt(hir_id).map(|substs| (def_id, substs))
+            }
+            _ => None,
+        };
+        let (def_id, substs) = match (method, &n_ty.sty) {
+            (Some(method), _) => method,
+            (None, &ty::TyFnDef(def_id, substs)) => (def_id, substs),
+            _ => return,
+        };
+
+        let tys: Vec<_> = substs.types().map(|ty| ty.to_string()).collect();
+        self.tcx().sess.span_note_without_error(
+            span,
+            &format!("dump-mono-calls: `{}` with substs [{}]",
+                     self.tcx().item_path_str(def_id),
+                     tys.join(", ")));
     }
 
     fn visit_adjustments(&mut self, span: Span, hir_id: hir::HirId) {
@@ -425,6 +1709,18 @@ impl<'cx, 'gcx, 'tcx> WritebackCx<'cx, 'gcx, 'tcx> {
                 local_id,
             };
             let fn_sig = self.resolve(fn_sig, &hir_id);
+
+            // Liberation replaces the late-bound regions of a signature with
+            // free regions scoped to the body, including for closures whose
+            // signature is higher-ranked (e.g. `for<'a> Fn(&'a u8)`). A bound
+            // region surviving here would be silently mistaken for one of an
+            // enclosing binder by later passes.
+            if fn_sig.has_escaping_regions() {
+                span_bug!(hir_id.to_span(&self.fcx.tcx),
+                          "liberated fn sig `{:?}` has escaping late-bound regions",
+                          fn_sig);
+            }
+
             self.tables.liberated_fn_sigs_mut().insert(hir_id, fn_sig.clone());
         }
     }
@@ -439,17 +1735,44 @@ impl<'cx, 'gcx, 'tcx> WritebackCx<'cx, 'gcx, 'tcx> {
                 owner: common_local_id_root.index,
                 local_id,
             };
-            let ftys = self.resolve(ftys, &hir_id);
+            let ftys = self.resolve(ftys, &self.fru_base_span(hir_id));
             self.tables.fru_field_types_mut().insert(hir_id, ftys);
         }
     }
 
+    // The field types of a functional record update are taken from its
+    // `..base` expression, so that is where failures to resolve them are
+    // reported.
+    fn fru_base_span(&self, hir_id: hir::HirId) -> Span {
+        let node_id = self.tcx().hir.definitions().find_node_for_hir_id(hir_id);
+        match self.tcx().hir.expect_expr(node_id).node {
+            hir::ExprStruct(_, _, Some(ref base)) => base.span,
+            _ => hir_id.to_span(&self.fcx.tcx),
+        }
+    }
+
     fn resolve<T>(&self, x: &T, span: &Locatable) -> T::Lifted
         where T: TypeFoldable<'tcx> + ty::Lift<'gcx>
     {
-        let x = x.fold_with(&mut Resolver::new(self.fcx, span, self.body));
+        let x = x.fold_with(&mut Resolver::new(self.fcx,
+                                               span,
+                                               self.body,
+                                               &self.err_ty_count,
+                                               &self.infer_vars,
+                                               &self.buffered_errors));
         if let Some(lifted) = self.tcx().lift_to_global(&x) {
             lifted
+        } else if self.tcx().sess.opts.debugging_opts.writeback_validate_lift {
+            let span = span.to_span(&self.fcx.tcx);
+            self.tcx().sess.diagnostic().span_bug_no_panic(span, &format!(
+                "writeback: `{:?}` missing from the global type context", x));
+            let x = x.fold_with(&mut LiftFallback { tcx: self.tcx() });
+            match self.tcx().lift_to_global(&x) {
+                Some(lifted) => lifted,
+                None => {
+                    span_bug!(span, "writeback: `{:?}` cannot be lifted even after fallback", x)
+                }
+            }
         } else {
             span_bug!(span.to_span(&self.fcx.tcx),
                       "writeback: `{:?}` missing from the global type context",
@@ -484,6 +1807,45 @@ impl Locatable for hir::HirId {
     }
 }
 
+///////////////////////////////////////////////////////////////////////////
+// The LiftFallback. Under `-Z writeback-validate-lift`, this replaces the
+// parts of a value that are missing from the global type context with
+// `types.err` and `'static`, so that writeback can go on after reporting
+// a failure to lift it.
+
+struct LiftFallback<'cx, 'gcx: 'cx+'tcx, 'tcx: 'cx> {
+    tcx: TyCtxt<'cx, 'gcx, 'tcx>,
+}
+
+impl<'cx, 'gcx, 'tcx> TypeFolder<'gcx, 'tcx> for LiftFallback<'cx, 'gcx, 'tcx> {
+    fn tcx<'a>(&'a self) -> TyCtxt<'a, 'gcx, 'tcx> {
+        self.tcx
+    }
+
+    fn fold_ty(&mut self, t: Ty<'tcx>) -> Ty<'tcx> {
+        if self.tcx.lift_to_global(&t).is_some() {
+            return t;
+        }
+
+        // Rebuilding the type from lifted components interns it globally,
+        // unless the type itself is local, like an inference variable.
+        let t = t.super_fold_with(self);
+        if self.tcx.lift_to_global(&t).is_some() {
+            t
+        } else {
+            self.tcx.types.err
+        }
+    }
+
+    fn fold_region(&mut self, r: ty::Region<'tcx>) -> ty::Region<'tcx> {
+        if self.tcx.lift_to_global(&r).is_some() {
+            r
+        } else {
+            self.tcx.types.re_static
+        }
+    }
+}
+
 ///////////////////////////////////////////////////////////////////////////
 // The Resolver. This is the type folding engine that detects
 // unresolved types and so forth.
@@ -493,10 +1855,24 @@ struct Resolver<'cx, 'gcx: 'cx+'tcx, 'tcx: 'cx> {
     infcx: &'cx InferCtxt<'cx, 'gcx, 'tcx>,
     span: &'cx Locatable,
     body: &'gcx hir::Body,
+    err_ty_count: &'cx Cell<usize>,
+    infer_vars: &'cx RefCell<FxHashMap<ty::TyVid, Ty<'tcx>>>,
+    buffered_errors: &'cx RefCell<Option<Vec<Diagnostic>>>,
+
+    // The types already fully resolved by this resolver. The same type
+    // often appears several times in a value, e.g. in the adjustments of
+    // an expression. Failed resolutions are not cached, so that each of
+    // them is counted.
+    resolved_tys: FxHashMap<Ty<'tcx>, Ty<'tcx>>,
 }
 
 impl<'cx, 'gcx, 'tcx> Resolver<'cx, 'gcx, 'tcx> {
-    fn new(fcx: &'cx FnCtxt<'cx, 'gcx, 'tcx>, span: &'cx Locatable, body: &'gcx hir::Body)
+    fn new(fcx: &'cx FnCtxt<'cx, 'gcx, 'tcx>,
+           span: &'cx Locatable,
+           body: &'gcx hir::Body,
+           err_ty_count: &'cx Cell<usize>,
+           infer_vars: &'cx RefCell<FxHashMap<ty::TyVid, Ty<'tcx>>>,
+           buffered_errors: &'cx RefCell<Option<Vec<Diagnostic>>>)
         -> Resolver<'cx, 'gcx, 'tcx>
     {
         Resolver {
@@ -504,12 +1880,45 @@ impl<'cx, 'gcx, 'tcx> Resolver<'cx, 'gcx, 'tcx> {
             infcx: fcx,
             span,
             body,
+            err_ty_count,
+            infer_vars,
+            buffered_errors,
+            resolved_tys: FxHashMap(),
+        }
+    }
+
+    fn record_infer_vars(&self, t: Ty<'tcx>) {
+        let mut infer_vars = self.infer_vars.borrow_mut();
+        for ty in t.walk() {
+            if let ty::TyInfer(ty::TyVar(vid)) = ty.sty {
+                infer_vars.entry(vid).or_insert_with(|| {
+                    self.infcx.fully_resolve(&ty).unwrap_or(self.tcx.types.err)
+                });
+            }
         }
     }
 
     fn report_error(&self, t: Ty<'tcx>) {
-        if !self.tcx.sess.has_errors() {
-            self.infcx.need_type_info(Some(self.body.id()), self.span.to_span(&self.tcx), t);
+        if self.tcx.sess.has_errors() {
+            return;
+        }
+
+        let body_id = Some(self.body.id());
+        let span = self.span.to_span(&self.tcx);
+        self.emit_or_buffer(self.infcx.need_type_info_err(body_id, span, t));
+    }
+
+    fn emit_or_buffer(&self, mut err: DiagnosticBuilder) {
+        match *self.buffered_errors.borrow_mut() {
+            // Like when emitting, only the first error is reported, as the
+            // others are usually caused by it.
+            Some(ref mut errors) => {
+                if errors.is_empty() {
+                    errors.push((*err).clone());
+                }
+                err.cancel();
+            }
+            None => err.emit(),
         }
     }
 }
@@ -520,12 +1929,24 @@ impl<'cx, 'gcx, 'tcx> TypeFolder<'gcx, 'tcx> for Resolver<'cx, 'gcx, 'tcx> {
     }
 
     fn fold_ty(&mut self, t: Ty<'tcx>) -> Ty<'tcx> {
+        if self.tcx.sess.opts.debugging_opts.dump_infer_vars {
+            self.record_infer_vars(t);
+        }
+
+        if let Some(&resolved) = self.resolved_tys.get(&t) {
+            return resolved;
+        }
+
         match self.infcx.fully_resolve(&t) {
-            Ok(t) => t,
+            Ok(resolved) => {
+                self.resolved_tys.insert(t, resolved);
+                resolved
+            }
             Err(_) => {
                 debug!("Resolver::fold_ty: input type `{:?}` not fully resolvable",
                        t);
                 self.report_error(t);
+                self.err_ty_count.set(self.err_ty_count.get() + 1);
                 self.tcx().types.err
             }
         }
@@ -543,6 +1964,31 @@ impl<'cx, 'gcx, 'tcx> TypeFolder<'gcx, 'tcx> for Resolver<'cx, 'gcx, 'tcx> {
     }
 }
 
+// Maps the name of a primitive type to the type, for `-Z resolve-ambiguous-as`.
+fn primitive_ty_named<'a, 'gcx, 'tcx>(tcx: TyCtxt<'a, 'gcx, 'tcx>, name: &str)
+                                      -> Option<Ty<'tcx>> {
+    let types = &tcx.types;
+    Some(match name {
+        "bool" => types.bool,
+        "char" => types.char,
+        "isize" => types.isize,
+        "i8" => types.i8,
+        "i16" => types.i16,
+        "i32" => types.i32,
+        "i64" => types.i64,
+        "i128" => types.i128,
+        "usize" => types.usize,
+        "u8" => types.u8,
+        "u16" => types.u16,
+        "u32" => types.u32,
+        "u64" => types.u64,
+        "u128" => types.u128,
+        "f32" => types.f32,
+        "f64" => types.f64,
+        _ => return None,
+    })
+}
+
 ///////////////////////////////////////////////////////////////////////////
 // During type check, we store promises with the result of trait
 // lookup rather than the actual results (because the results are not
@@ -225,6 +225,10 @@ impl<'a, 'tcx> AstConv<'tcx, 'tcx> for ItemCtxt<'a, 'tcx> {
     fn record_ty(&self, _hir_id: hir::HirId, _ty: Ty<'tcx>, _span: Span) {
         // no place to record types from signatures?
     }
+
+    fn record_unnormalized_ty(&self, _hir_id: hir::HirId, _ty: Ty<'tcx>) {
+        // signatures have no tables to record them in either
+    }
 }
 
 fn type_param_predicates<'a, 'tcx>(tcx: TyCtxt<'a, 'tcx, 'tcx>,
@@ -329,8 +329,18 @@ pub fn check_crate<'a, 'tcx>(tcx: TyCtxt<'a, 'tcx, 'tcx>)
 
     time(time_passes, "item-types checking", || check::check_item_types(tcx))?;
 
+    tcx.sess.track_errors(|| {
+        time(time_passes, "buffered writeback errors testing", ||
+             check::tables_test::test_buffered_writeback_errors(tcx));
+    })?;
+
     time(time_passes, "item-bodies checking", || check::check_item_bodies(tcx))?;
 
+    tcx.sess.track_errors(|| {
+        time(time_passes, "typeck tables testing", ||
+             check::tables_test::test_tables(tcx));
+    })?;
+
     check_unused::check_crate(tcx);
     check_for_entry_fn(tcx);
 
@@ -0,0 +1,29 @@
+// Copyright 2017 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+// Test that the `closure_upvar_tys` query returns the resolved upvar types
+// of every closure in the crate, in the order of their upvars.
+
+#![feature(rustc_attrs)]
+
+fn other(s: &str) -> usize {
+    let f = |n: usize| s.len() + n; //~ ERROR upvar types [`&&str`]
+    f(1)
+}
+
+#[rustc_dump_closure_upvar_tys]
+fn main() {
+    let a = 1u8;
+    let b = String::new();
+    let by_ref = || a + 1; //~ ERROR upvar types [`&u8`]
+    let by_move = move || b.len() + a as usize;
+    //~^ ERROR upvar types [`std::string::String`, `u8`]
+    let none = || 5; //~ ERROR upvar types []
+}
@@ -0,0 +1,50 @@
+// Copyright 2017 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+// compile-flags: -Z collection-annotation-hints
+
+// Test that a collection whose element type is never determined gets a
+// suggestion naming the element type, while other types keep the plain
+// annotation error.
+
+use std::collections::HashMap;
+
+struct Stack<T>(Vec<T>);
+
+impl<T> Stack<T> {
+    fn new() -> Stack<T> {
+        Stack(Vec::new())
+    }
+}
+
+fn vec() {
+    let v = Vec::new();
+    //~^ ERROR type annotations needed [E0282]
+    //~| NOTE cannot infer type for `T`
+    //~| NOTE consider giving `v` a type
+    //~| HELP consider giving `v` a type that names the element type of the `Vec`, as in
+}
+
+fn map() {
+    let m = HashMap::new();
+    //~^ ERROR type annotations needed [E0282]
+    //~| NOTE cannot infer type for `K`
+    //~| NOTE consider giving `m` a type
+    //~| HELP names the element types of the `HashMap`, as in `let m: HashMap<K, V>`
+}
+
+fn not_a_collection() {
+    let s = Stack::new();
+    //~^ ERROR type annotations needed [E0282]
+    //~| NOTE cannot infer type for `T`
+    //~| NOTE consider giving `s` a type
+}
+
+fn main() {}
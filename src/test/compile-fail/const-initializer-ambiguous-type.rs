@@ -0,0 +1,21 @@
+// Copyright 2017 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+// Test that a type left ambiguous in the initializer of a `const` item is
+// reported at the ambiguous expression, not at the item.
+
+const RESOLVED: u32 = 1 + 2;
+
+const AMBIGUOUS: usize =
+    ::std::mem::size_of_val(&None); //~ ERROR type annotations needed
+
+fn main() {
+    let _ = (RESOLVED, AMBIGUOUS);
+}
@@ -0,0 +1,31 @@
+// Copyright 2017 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+// compile-flags: -Z dump-closure-sigs
+
+// Test that `-Z dump-closure-sigs` reports the resolved signature of each
+// closure, including argument types that were only inferred from uses.
+
+#![feature(rustc_attrs)]
+
+fn call<F: Fn(&str) -> usize>(f: F) -> usize {
+    f("abc")
+}
+
+#[rustc_error]
+fn main() { //~ ERROR compilation successful
+    let inc = |x| x + 1; //~ NOTE closure signature: `fn(u8) -> u8`
+    inc(1u8);
+
+    let pair = |a, b: char| (a, b); //~ NOTE closure signature: `fn(bool, char) -> (bool, char)`
+    pair(true, 'x');
+
+    call(|s| s.len()); //~ NOTE closure signature: `fn(&str) -> usize`
+}
@@ -0,0 +1,25 @@
+// Copyright 2017 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+// compile-flags: -Z dump-infer-vars
+
+// Test that `-Z dump-infer-vars` reports the type each inference variable
+// of a body resolved to, at the span the variable was created for.
+
+#![feature(rustc_attrs)]
+#![allow(dead_code)]
+
+fn two_vars() {
+    let _x: u8 = Default::default(); //~ NOTE resolved to `u8`
+    let _y: (u16, bool) = Default::default(); //~ NOTE resolved to `(u16, bool)`
+}
+
+#[rustc_error]
+fn main() {} //~ ERROR compilation successful
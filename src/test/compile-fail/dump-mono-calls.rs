@@ -0,0 +1,31 @@
+// Copyright 2017 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+// compile-flags: -Z dump-mono-calls
+
+// Test that `-Z dump-mono-calls` reports the resolved callee and type
+// substs of calls to generic functions, constructors and methods.
+
+#![feature(rustc_attrs)]
+
+fn generic<T>(t: T) -> T {
+    t
+}
+
+#[rustc_error]
+fn main() { //~ ERROR compilation successful
+    generic(1u8); //~ NOTE `generic` with substs [u8]
+    Some(2u16).unwrap();
+    //~^ NOTE Some` with substs [u16]
+    //~| NOTE unwrap` with substs [u16]
+    let _ = generic::<fn(char) -> char>(generic);
+    //~^ NOTE `generic` with substs [fn(char) -> char]
+    //~| NOTE `generic` with substs [char]
+}
@@ -0,0 +1,33 @@
+// Copyright 2017 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+// Test that a condition that is not a `bool` is reported with the type it
+// was finally inferred to have, when that type was not known at the time
+// of the mismatch.
+
+fn main() {
+    let mut x = None;
+    if x {}
+    //~^ ERROR mismatched types
+    //~| NOTE expected bool, found enum `std::option::Option`
+    //~| NOTE expected type `bool`
+    //~| NOTE the condition was inferred to have type `std::option::Option<u8>`
+    x = Some(5u8);
+
+    let y = 5;
+    while y {}
+    //~^ ERROR mismatched types
+    //~| NOTE expected bool, found integral variable
+    //~| NOTE expected type `bool`
+    //~| NOTE the condition was inferred to have type `i32`
+
+    let z = Default::default();
+    if z {}
+}
@@ -0,0 +1,44 @@
+// Copyright 2016 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+// Check that E0564 explains where the offending lifetimes come from, and
+// reports them once per `impl Trait`.
+
+#![feature(conservative_impl_trait)]
+
+fn stack() -> impl Copy {
+    //~^ ERROR only named lifetimes are allowed in `impl Trait`
+    //~| NOTE the lifetime in the hidden type is only valid for
+    let x = 0;
+    &x
+}
+
+fn late_bound(x: &i32) -> impl Copy {
+    //~^ ERROR only named lifetimes are allowed in `impl Trait`
+    //~| NOTE the lifetime in the hidden type is only valid for the anonymous lifetime #1
+    x
+}
+
+fn captured_by_closure() -> impl Fn() -> i32 {
+    //~^ ERROR only named lifetimes are allowed in `impl Trait`
+    //~| NOTE the lifetime in the hidden type is only valid for
+    let x = 0;
+    || x
+}
+
+// All the offending lifetimes of a type are reported together.
+fn two_late_bound(x: &i32, y: &u32) -> impl Copy {
+    //~^ ERROR were found in the type
+    //~| NOTE the lifetime in the hidden type is only valid for the anonymous lifetime #1
+    //~| NOTE the lifetime in the hidden type is only valid for the anonymous lifetime #2
+    (x, y)
+}
+
+fn main() {}
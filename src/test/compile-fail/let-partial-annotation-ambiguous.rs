@@ -0,0 +1,24 @@
+// Copyright 2017 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+// Test that when the inferred part of a partial `let` annotation cannot be
+// inferred, the error points at the binding of that part, rather than at
+// the whole pattern or at the initializer.
+
+fn make<T>() -> (u8, T) {
+    loop {}
+}
+
+fn main() {
+    let (a,
+         b): (u8, _) //~ ERROR type annotations needed
+        = make();
+    let _ = a;
+}
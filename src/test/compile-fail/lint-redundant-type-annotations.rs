@@ -0,0 +1,29 @@
+// Copyright 2017 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+#![deny(redundant_type_annotations)]
+#![allow(unused_variables)]
+
+fn main() {
+    // Without the annotation, `a.pow(2)` would be ambiguous.
+    let a: i32 = 5;
+    let _ = a.pow(2);
+    let b: u8 = 5;
+    let c: f64 = 1.5;
+    let d: f32 = 1.5;
+    let e: u64 = 5u64; //~ ERROR type annotation is redundant
+    let f: bool = true; //~ ERROR type annotation is redundant
+    let g: _ = 5;
+    let h: Option<i32> = None;
+    let i: &str = "literal"; //~ ERROR type annotation is redundant
+    let j: i32 = b as i32;
+    let k: char = 'k'; //~ ERROR type annotation is redundant
+    let l: f32 = 1.5f32; //~ ERROR type annotation is redundant
+}
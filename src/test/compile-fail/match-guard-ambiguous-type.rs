@@ -0,0 +1,32 @@
+// Copyright 2017 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+// Test that a type left ambiguous inside a match guard is reported at the
+// guard, and that guards calling generic methods otherwise resolve.
+
+fn resolved(o: Option<u8>) -> u8 {
+    match o {
+        Some(x) if x.eq(&Default::default()) => x,
+        Some(x) if Some(x).map(|y| y > 1).unwrap_or(false) => x,
+        _ => 0,
+    }
+}
+
+fn ambiguous(o: Option<u8>) -> u8 {
+    match o {
+        Some(x) if Vec::new().is_empty() => x, //~ ERROR type annotations needed
+        _ => 0,
+    }
+}
+
+fn main() {
+    resolved(None);
+    ambiguous(None);
+}
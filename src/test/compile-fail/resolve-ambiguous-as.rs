@@ -0,0 +1,30 @@
+// Copyright 2017 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+// compile-flags: -Z resolve-ambiguous-as=u32
+
+// Test that `-Z resolve-ambiguous-as` reports the types that the nodes
+// depending on an ambiguous type variable would have if it were `u32`.
+
+fn main() {
+    let x = None;
+    //~^ ERROR type annotations needed
+    //~| NOTE cannot infer type for `T`
+    //~| NOTE consider giving `x` a type
+    //~| NOTE assuming this type is `u32`
+    //~| NOTE would have type `std::option::Option<u32>`
+    //~| NOTE would have type `std::option::Option<u32>`
+    //~| NOTE would have type `std::option::Option<u32>`
+    let y = [x];
+    //~^ NOTE would have type `[std::option::Option<u32>; 1]`
+    //~| NOTE would have type `[std::option::Option<u32>; 1]`
+    //~| NOTE would have type `[std::option::Option<u32>; 1]`
+    //~| NOTE would have type `std::option::Option<u32>`
+}
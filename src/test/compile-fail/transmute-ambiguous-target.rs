@@ -0,0 +1,26 @@
+// Copyright 2017 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+// Test that a `transmute` whose target type is never constrained is
+// reported as such at the call.
+
+use std::mem::transmute;
+
+fn main() {
+    let x = 5u32;
+    unsafe {
+        let _ = transmute::<u32, [u8; 4]>(x);
+        transmute(x);
+        //~^ ERROR type annotations needed
+        //~| NOTE cannot infer type for `_`
+        //~| NOTE cannot infer the target type of this `transmute`
+        //~| HELP specify both types, as in `transmute::<Source, Target>(value)`
+    }
+}
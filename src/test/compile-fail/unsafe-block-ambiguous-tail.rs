@@ -0,0 +1,23 @@
+// Copyright 2017 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+// Test that an ambiguous type in the tail expression of an `unsafe` block
+// is reported inside the block, rather than on the block itself.
+
+unsafe fn ambiguous<T>() -> T {
+    loop {}
+}
+
+fn main() {
+    unsafe {
+        ambiguous()
+        //~^ ERROR type annotations needed
+    };
+}
@@ -0,0 +1,25 @@
+// Copyright 2017 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+// Test that a field of unknown type in the construction of a struct-like
+// enum variant is reported at the field, not at the path of the variant.
+
+enum E<A, B> {
+    V { a: A, b: B },
+}
+
+fn main() {
+    let _ = E::V {
+        a: 1u8,
+        b: Default::default(),
+        //~^ ERROR type annotations needed
+        //~| NOTE cannot infer type for `B`
+    };
+}
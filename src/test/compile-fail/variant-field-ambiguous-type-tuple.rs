@@ -0,0 +1,25 @@
+// Copyright 2017 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+// Test that an argument of unknown type in the construction of a tuple-like
+// enum variant is reported at the argument, not at the path of the variant.
+
+enum E<A, B> {
+    T(A, B),
+}
+
+fn main() {
+    let _ = E::T(
+        1u8,
+        Default::default(),
+        //~^ ERROR type annotations needed
+        //~| NOTE cannot infer type for `B`
+    );
+}
@@ -0,0 +1,35 @@
+// Copyright 2017 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+// Test that the resolved types of the expressions in anonymous constant
+// bodies, and of the closures in them, are available from the
+// `typeck_tables_of` their `DefId`.
+
+#![feature(rustc_attrs)]
+
+#[rustc_dump_anon_const_tys]
+fn test() {
+    let _a: [u8; 1 + 2] = [0; 3];
+    //~^ ERROR anon const expr `usize`
+    //~| ERROR anon const expr `usize`
+    //~| ERROR anon const expr `usize`
+    //~| ERROR anon const expr `usize`
+
+    // Closures in anonymous constants are resolved with the constant.
+    let _b = [0u8; (|x: usize| x + 1)(1)];
+    //~^ ERROR anon const expr `usize`
+    //~| ERROR anon const expr `[closure@
+    //~| ERROR anon const expr `usize`
+    //~| ERROR anon const expr `usize`
+    //~| ERROR anon const expr `usize`
+    //~| ERROR anon const expr `usize`
+}
+
+fn main() {}
@@ -0,0 +1,37 @@
+// Copyright 2017 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+// Test that `-Z assumed-bound-hints` points out the bound under which a
+// qualified path type that does not resolve would, and that the type is
+// still reported as an error.
+
+// compile-flags: -Z assumed-bound-hints
+
+trait Tr {
+    type Item;
+}
+
+impl Tr for u8 {
+    type Item = u32;
+}
+
+fn unbounded<T>() {
+    let _: <T as Tr>::Item;
+    //~^ ERROR the trait bound `T: Tr` is not satisfied
+    //~| NOTE the trait `Tr` is not implemented for `T`
+    //~| NOTE adding the bound `T: Tr` would let this type resolve to `<T as Tr>::Item`
+}
+
+fn bounded<T: Tr>() {
+    let _: <T as Tr>::Item;
+    let _: <u8 as Tr>::Item;
+}
+
+fn main() {}
@@ -0,0 +1,24 @@
+// Copyright 2017 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+// Test that the tail expression of a block has the type of the block, even
+// when it is coerced, and that blocks ending in a statement have no tail.
+
+#![feature(rustc_attrs)]
+
+#[rustc_dump_block_tail_tys]
+fn test(r: &[u8; 2]) -> u16 { //~ ERROR block `u16` with tail `u16`
+    let s: &[u8] = { r }; //~ ERROR block `&[u8]` with tail `&[u8]`
+    let n = { s.len(); }; //~ ERROR block `()` without tail
+    n;
+    { 1 } //~ ERROR block `u16` with tail `u16`
+}
+
+fn main() {}
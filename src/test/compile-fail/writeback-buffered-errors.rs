@@ -0,0 +1,25 @@
+// Copyright 2017 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+// Test that writeback can return the errors about types it could not
+// resolve instead of emitting them. With this attribute, the buffered
+// errors are emitted again with a different message, and the original
+// E0282 is not emitted.
+
+#![feature(rustc_attrs)]
+
+#[rustc_dump_buffered_writeback_errors]
+fn buffered() {
+    // Nothing requires anything of the element type of an empty array, so
+    // the error comes from writeback rather than from trait selection.
+    let x = []; //~ ERROR buffered: type annotations needed
+}
+
+fn main() {}
@@ -0,0 +1,29 @@
+// Copyright 2017 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+// Test that the argument types of a closure are reported in order, once
+// inferred from the context the closure is used in.
+
+#![feature(rustc_attrs)]
+
+fn apply<F: Fn(i32, u64) -> u64>(f: F) -> u64 {
+    f(1, 2)
+}
+
+#[rustc_dump_closure_arg_tys]
+fn test() {
+    apply(|x, y| x as u64 + y); //~ ERROR arguments `i32`, `u64`
+    let pair = |(a, b), c: bool| if c { a } else { b }; //~ ERROR arguments `(char, char)`, `bool`
+    pair(('a', 'b'), true);
+    let unit = || (); //~ ERROR arguments
+    unit();
+}
+
+fn main() {}
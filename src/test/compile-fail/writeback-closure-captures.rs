@@ -0,0 +1,37 @@
+// Copyright 2017 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+// Test that the captures of a closure and of a closure nested in it can be
+// retrieved separately, even where they capture the same variables.
+
+#![feature(rustc_attrs)]
+
+#[rustc_dump_closure_captures]
+fn test() {
+    let mut a = 1;
+    let b = String::new();
+    let c = vec![2u8];
+    let mut outer = || {
+    //~^ ERROR captures `a` by mut ref
+    //~| ERROR captures `b` by ref
+    //~| ERROR captures `c` by value
+        a += 1;
+        let n = {
+            let inner = || b.len() + c.len(); //~ ERROR captures `b` by ref
+            //~^ ERROR captures `c` by ref
+            inner()
+        };
+        let moved = move || c; //~ ERROR captures `c` by value
+        n + moved().len()
+    };
+    outer();
+}
+
+fn main() {}
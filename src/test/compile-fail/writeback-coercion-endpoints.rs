@@ -0,0 +1,24 @@
+// Copyright 2017 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+// Test that the types an expression is coerced from and to are reported,
+// and that expressions without adjustments are not considered coerced.
+
+#![feature(rustc_attrs)]
+
+#[rustc_dump_coercion_endpoints]
+fn test(r: &&u8) {
+    let _: &[u8] = &[1u8, 2]; //~ ERROR coerced from `&[u8; 2]` to `&[u8]`
+    let _: &u8 = r; //~ ERROR coerced from `&&u8` to `&u8`
+    let _ = 5u8; //~ ERROR not coerced
+    let _: &&u8 = r; //~ ERROR not coerced
+}
+
+fn main() {}
@@ -0,0 +1,30 @@
+// Copyright 2017 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+// Test that the element type of array literals and `vec!` invocations is
+// reported, unified across the elements.
+
+#![feature(rustc_attrs)]
+
+fn foo() {}
+fn bar() {}
+
+#[rustc_dump_collection_elem_tys]
+fn test() {
+    let _ = vec![1u8, 2]; //~ ERROR element type `u8`
+    let _ = vec![1.5; 3]; //~ ERROR element type `f64`
+    let _: Vec<u16> = vec![]; //~ ERROR element type `u16`
+    let _ = [foo, bar]; //~ ERROR element type `fn()`
+    let _ = [0; 4]; //~ ERROR element type `i32`
+    let _ = Vec::<u8>::new(); //~ ERROR no element type
+    let _ = 5; //~ ERROR no element type
+}
+
+fn main() {}
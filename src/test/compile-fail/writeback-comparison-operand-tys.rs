@@ -0,0 +1,26 @@
+// Copyright 2017 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+// Test that the operand types of comparisons are reported before the
+// autoref of overloaded comparisons. A literal compared with a `u8` is
+// inferred to be a `u8`, not coerced to one.
+
+#![feature(rustc_attrs)]
+
+#[rustc_dump_comparison_operand_tys]
+fn test(x: u8, s: String) -> bool {
+    let a = x == 5; //~ ERROR comparison of `u8` with `u8`
+    let b = 2.0 < 1.5f32; //~ ERROR comparison of `f32` with `f32`
+    let c = s != "s"; //~ ERROR comparison of `std::string::String` with `&'static str`
+    let _ = x + 1; // not a comparison
+    a && b && c
+}
+
+fn main() {}
@@ -0,0 +1,32 @@
+// Copyright 2017 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+// Test that the resolved types of explicit enum discriminants are available,
+// along with the type given by the `repr` of the enum.
+
+#![feature(rustc_attrs)]
+
+const BASE: u8 = 8;
+
+#[repr(u8)]
+#[rustc_dump_discriminant_tys]
+enum Small {
+    A = 1 + 2, //~ ERROR discriminant `u8`, repr `u8`
+    B,
+    C = BASE | 1, //~ ERROR discriminant `u8`, repr `u8`
+}
+
+#[rustc_dump_discriminant_tys]
+enum Default {
+    X = -1, //~ ERROR discriminant `isize`, repr `isize`
+    Y,
+}
+
+fn main() {}
@@ -0,0 +1,35 @@
+// Copyright 2017 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+// compile-flags: -Z writeback-emit-spans
+
+// Test that `-Z writeback-emit-spans` reports the kind of each node along
+// with its resolved type.
+
+#![feature(rustc_attrs)]
+#![allow(dead_code)]
+
+fn id(y: u16) -> u16 { y }
+//~^ NOTE arg `u16`
+//~| NOTE pat `u16`
+//~| NOTE expr `u16`
+//~| NOTE block `u16`
+//~| NOTE expr `u16`
+
+#[rustc_error]
+fn main() { //~ ERROR compilation successful
+    //~^ NOTE expr `()`
+    //~| NOTE block `()`
+    let _x: u8 = 1;
+    //~^ NOTE local `u8`
+    //~| NOTE pat `u8`
+    //~| NOTE ty `u8`
+    //~| NOTE expr `u8`
+}
@@ -0,0 +1,33 @@
+// Copyright 2017 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+// compile-flags: -Z writeback-err-detail
+
+// Test that `-Z writeback-err-detail` counts the nodes whose type is the
+// error type separately from those whose type only contains it. In
+// `broken`, the local, the pattern and the type of `_x` are errors, while
+// for `_y` only the inner `Missing` is, and the local, the pattern and the
+// type `Option<Missing>` reference it.
+
+fn clean() { //~ NOTE `clean`: 0 nodes have type `[type error]`, 0 other nodes
+    let _x = 1u8;
+}
+
+fn broken() { //~ NOTE `broken`: 4 nodes have type `[type error]`, 3 other nodes
+    let _x: Missing; //~ ERROR cannot find type `Missing` in this scope
+    //~| NOTE not found in this scope
+    let _y: Option<Missing>; //~ ERROR cannot find type `Missing` in this scope
+    //~| NOTE not found in this scope
+}
+
+fn main() { //~ NOTE `main`: 0 nodes have type `[type error]`, 0 other nodes
+    clean();
+    broken();
+}
@@ -0,0 +1,32 @@
+// Copyright 2017 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+// compile-flags: -Z writeback-err-stats
+
+// Test that `-Z writeback-err-stats` counts the types that fell back to
+// the error type in each body: a single ambiguous variable cascades into
+// the pattern, the local, the `None` expression and its substs, and the
+// tables are marked as tainted, while a clean body reports none.
+
+fn clean() { //~ NOTE `clean`: 0 types fell back to `[type error]` while resolving 8 nodes
+    let x = 1u8;
+    let _y = x;
+}
+
+fn ambiguous() { //~ NOTE 4 types fell back to `[type error]` while resolving 5 nodes (tainted
+    let x = None; //~ ERROR type annotations needed
+    //~| NOTE cannot infer type
+    //~| NOTE consider giving `x` a type
+}
+
+fn main() { //~ NOTE `main`: 0 types fell back to `[type error]`
+    clean();
+    ambiguous();
+}
@@ -0,0 +1,32 @@
+// Copyright 2017 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+// Test that the element type of a `for` loop is the resolved type of its
+// loop pattern, for plain and destructuring patterns.
+
+#![feature(rustc_attrs)]
+
+#[rustc_dump_for_loop_elem_tys]
+fn test() {
+    for x in vec![1u8] { //~ ERROR for loop element `u8`
+        let _ = x;
+    }
+
+    for (i, c) in "ab".chars().enumerate() { //~ ERROR for loop element `(usize, char)`
+        let _ = (i, c);
+    }
+
+    let v = vec![(1u16, 'a')];
+    for &(n, _) in &v { //~ ERROR for loop element `&(u16, char)`
+        while n > 1 {} // not a `for` loop
+    }
+}
+
+fn main() {}
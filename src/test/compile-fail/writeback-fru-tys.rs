@@ -0,0 +1,28 @@
+// Copyright 2017 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+// Test that both the field types and the type of the base expression
+// of a generic functional record update are recorded.
+
+#![feature(rustc_attrs)]
+
+#[derive(Default)]
+struct S<T> {
+    a: T,
+    b: Vec<T>,
+}
+
+#[rustc_dump_fru_tys]
+fn test() {
+    let _s = S { a: 1u8, ..Default::default() };
+    //~^ ERROR base `S<u8>`, fields [u8, std::vec::Vec<u8>]
+}
+
+fn main() {}
@@ -0,0 +1,33 @@
+// Copyright 2017 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+// Test that the resolved interior types of a generator can be used to
+// find the value that makes it non-`Send`.
+
+#![feature(generators, rustc_attrs)]
+
+use std::rc::Rc;
+
+#[rustc_dump_generator_interiors]
+fn test() {
+    let _ = || { //~ ERROR holds non-`Send` type `std::rc::Rc<u8>`
+        let r = Rc::new(1u8);
+        yield;
+        drop(r);
+    };
+
+    let _ = || { //~ ERROR all held types are `Send`
+        let b = Box::new(1u8);
+        yield;
+        drop(b);
+    };
+}
+
+fn main() {}
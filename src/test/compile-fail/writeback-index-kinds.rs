@@ -0,0 +1,30 @@
+// Copyright 2017 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+// Test that indexing arrays and slices with `usize` is recorded as
+// builtin, even when the index type is only inferred afterwards, while
+// indexing other types goes through `Index`.
+
+#![feature(rustc_attrs)]
+
+use std::collections::HashMap;
+
+#[rustc_dump_index_kinds]
+fn test(arr: [u8; 4], slice: &[u8], map: HashMap<u32, u8>) {
+    let _ = arr[0]; //~ ERROR builtin index
+    let _ = slice[1]; //~ ERROR builtin index
+    let i = Default::default();
+    let _ = arr[i]; //~ ERROR builtin index
+    let _: usize = i;
+    let _ = map[&2]; //~ ERROR overloaded index
+    let _ = &slice[1..]; //~ ERROR overloaded index
+}
+
+fn main() {}
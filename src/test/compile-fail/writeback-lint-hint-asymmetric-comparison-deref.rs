@@ -0,0 +1,34 @@
+// Copyright 2017 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+// Test that writeback flags comparisons where only one operand is
+// auto-dereferenced, but not symmetric comparisons or reborrows.
+
+#![feature(rustc_attrs)]
+
+struct S;
+
+// The only impl, so that the type of the right operand is known before it
+// is checked and can be coerced to.
+impl<'a> PartialEq<&'a S> for S {
+    fn eq(&self, _: &&'a S) -> bool { true }
+}
+
+#[rustc_dump_lint_hints]
+fn test(s: S, t: S, boxed: Box<S>, r: &&S, mut u: S) {
+    let _ = s == &boxed; //~ ERROR lint hint `AsymmetricComparisonDeref`
+    let _ = s == r; //~ ERROR lint hint `AsymmetricComparisonDeref`
+    let _ = s == &t;
+    let _ = s == &mut u;
+    let _ = &1 == &2;
+    let _ = 1 < 2;
+}
+
+fn main() {}
@@ -0,0 +1,25 @@
+// Copyright 2017 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+// Test that writeback flags `.clone()` calls on `Copy` receivers,
+// using the where-clauses of the body for generic receivers.
+
+#![feature(rustc_attrs)]
+
+#[rustc_dump_lint_hints]
+fn test<T: Copy, U: Clone>(x: u32, s: String, t: T, u: U) {
+    let _ = x.clone(); //~ ERROR lint hint `CloneOnCopy`
+    let _ = (&x).clone(); //~ ERROR lint hint `CloneOnCopy`
+    let _ = t.clone(); //~ ERROR lint hint `CloneOnCopy`
+    let _ = s.clone();
+    let _ = u.clone();
+}
+
+fn main() {}
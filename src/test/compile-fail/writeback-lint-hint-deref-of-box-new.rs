@@ -0,0 +1,25 @@
+// Copyright 2017 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+// Test that writeback flags dereferences of `Box::new(..)` calls, but not
+// of other boxes.
+
+#![feature(rustc_attrs)]
+
+#[rustc_dump_lint_hints]
+fn test(some_box: Box<u32>) {
+    let _ = *Box::new(5); //~ ERROR lint hint `DerefOfBoxNew`
+    let _ = *some_box;
+    let _ = *Box::<u8>::new(5); //~ ERROR lint hint `DerefOfBoxNew`
+    let b = Box::new(5);
+    let _ = *b;
+}
+
+fn main() {}
@@ -0,0 +1,32 @@
+// Copyright 2017 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+// Test that writeback flags `format_args!` arguments of type `()`, but not
+// other arguments nor unit values outside of formatting macros.
+
+#![feature(rustc_attrs)]
+
+fn unit_returning() {}
+
+fn number() -> u32 { 5 }
+
+#[rustc_dump_lint_hints]
+fn test(s: &str) {
+    let _ = format!("{:?}", unit_returning()); //~ ERROR lint hint `FormatUnitArgument`
+    let _ = format!("{} {:?}", number(), ()); //~ ERROR lint hint `FormatUnitArgument`
+    let _ = format!("{} {}", number(), s);
+    let _ = format!("no arguments");
+    let unit = unit_returning();
+    match (&unit,) {
+        (_,) => {}
+    }
+}
+
+fn main() {}
@@ -0,0 +1,36 @@
+// Copyright 2017 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+// Test that writeback flags integer literals that are out of range for
+// their inferred type, and only those.
+
+#![feature(rustc_attrs)]
+#![allow(overflowing_literals)]
+
+fn take_i16(_: i16) {}
+
+#[rustc_dump_lint_hints]
+fn test() {
+    let _: u8 = 300; //~ ERROR lint hint `OverflowingLiteral(300)`
+    let _: u8 = 255;
+    let _: i8 = 128; //~ ERROR lint hint `OverflowingLiteral(128)`
+    let _: i8 = -128;
+    let _: i8 = -129; //~ ERROR lint hint `OverflowingLiteral(129)`
+    let _: u64 = 18446744073709551615;
+    let _ = 300u8; //~ ERROR lint hint `OverflowingLiteral(300)`
+    let _ = 255u8;
+    let _ = -128i8;
+    let _: usize = 300;
+    take_i16(40000); //~ ERROR lint hint `OverflowingLiteral(40000)`
+    let x = 70000;
+    let _: u16 = x; //~^ ERROR lint hint `OverflowingLiteral(70000)`
+}
+
+fn main() {}
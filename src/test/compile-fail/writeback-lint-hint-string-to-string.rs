@@ -0,0 +1,36 @@
+// Copyright 2017 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+// Test that writeback flags `.to_string()` calls on `String` receivers, and
+// not on other types named `String`.
+
+#![feature(rustc_attrs)]
+
+mod shadow {
+    use std::fmt;
+
+    pub struct String;
+
+    impl fmt::Display for String {
+        fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+            f.write_str("shadow")
+        }
+    }
+}
+
+#[rustc_dump_lint_hints]
+fn test(s: String, n: i32, r: &str, t: shadow::String) {
+    let _ = s.to_string(); //~ ERROR lint hint `StringToString`
+    let _ = n.to_string();
+    let _ = r.to_string();
+    let _ = t.to_string();
+}
+
+fn main() {}
@@ -0,0 +1,26 @@
+// Copyright 2017 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+// Test that each local of a body is reported with whether its resolved
+// type needs drop, using the where-clauses of the body for generic types.
+
+#![feature(rustc_attrs)]
+
+#[rustc_dump_local_drop_tys]
+fn test<T: Copy, U>(t: T, u: U) {
+//~^ ERROR local `T`, no drop
+//~| ERROR local `U`, needs drop
+    let s = String::new(); //~ ERROR local `std::string::String`, needs drop
+    let n = 1u32; //~ ERROR local `u32`, no drop
+    let r = &s; //~ ERROR local `&std::string::String`, no drop
+    let _ = (t, u, n, r);
+}
+
+fn main() {}
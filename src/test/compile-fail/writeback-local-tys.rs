@@ -0,0 +1,26 @@
+// Copyright 2017 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+// Test that the locals of a body are listed in declaration order with
+// their resolved types, shadowed ones included.
+
+#![feature(rustc_attrs)]
+
+#[rustc_dump_local_tys]
+fn test(n: u16) { //~ ERROR local #0 `u16`
+    let a = 1u8; //~ ERROR local #1 `u8`
+    let (b, ref c) = (3i64, 2.0f64);
+    //~^ ERROR local #2 `i64`
+    //~| ERROR local #3 `&f64`
+    let a = vec![a]; //~ ERROR local #4 `std::vec::Vec<u8>`
+    let _ = (n, b, c, a);
+}
+
+fn main() {}
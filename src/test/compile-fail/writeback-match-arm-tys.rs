@@ -0,0 +1,38 @@
+// Copyright 2017 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+// Test that the type of each match arm is reported before its coercion to
+// the type of the match.
+
+#![feature(rustc_attrs)]
+
+fn foo() {}
+fn bar() {}
+
+#[rustc_dump_match_arm_tys]
+fn test(c: bool) {
+    let _ = match c {
+        //~^ ERROR arms `fn() {foo}`, `fn() {bar}` => `fn()`
+        true => foo,
+        false => bar,
+    };
+    let _: &[u8] = match c {
+        //~^ ERROR arms `&[u8; 3]`, `&[u8; 1]` => `&[u8]`
+        true => &[1, 2, 3],
+        false => &[1],
+    };
+    let _ = match c { //~ ERROR arms `u8`, `u8` => `u8`
+        true => 1u8,
+        false => 2,
+    };
+    let _ = c;
+}
+
+fn main() {}
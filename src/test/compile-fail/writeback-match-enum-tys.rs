@@ -0,0 +1,39 @@
+// Copyright 2017 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+// Test that the enum type of a match scrutinee is recorded fully
+// resolved, including through references, and that non-enum
+// scrutinees have no enum type.
+
+#![feature(rustc_attrs)]
+
+enum E<T> {
+    A(T),
+    B,
+}
+
+#[rustc_dump_match_enum_tys]
+fn test(n: u32) {
+    let e = E::A(Vec::new());
+    match e { //~ ERROR enum scrutinee `E<std::vec::Vec<u8>>`
+        E::A(mut v) => v.push(1u8),
+        E::B => {}
+    }
+
+    match &E::B::<i64> { //~ ERROR enum scrutinee `E<i64>`
+        &E::A(_) | &E::B => {}
+    }
+
+    match n { //~ ERROR non-enum scrutinee
+        _ => {}
+    }
+}
+
+fn main() {}
@@ -0,0 +1,25 @@
+// Copyright 2017 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+// Test that the types along a method call chain are reported in order,
+// from the innermost receiver to the result of the outermost call.
+
+#![feature(rustc_attrs)]
+
+#[rustc_dump_method_chain_tys]
+fn test() {
+    let _ = "a b".to_string().len().checked_add(1);
+    //~^ ERROR chain `&'static str` -> `std::string::String` -> `usize` -> `std::option::Option
+    let n = 5u8;
+    let _ = n.pow(2); //~ ERROR chain `u8` -> `u8`
+    let _ = n + 1; // not a method call
+}
+
+fn main() {}
@@ -0,0 +1,58 @@
+// Copyright 2017 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+// Test the output types of overloaded and builtin operators.
+
+#![feature(rustc_attrs)]
+
+use std::ops::{Add, AddAssign, Neg};
+
+#[derive(Copy, Clone)]
+struct Meters(u32);
+
+struct Area(u64);
+
+impl Add for Meters {
+    type Output = Area;
+    fn add(self, rhs: Meters) -> Area {
+        Area(self.0 as u64 * rhs.0 as u64)
+    }
+}
+
+impl AddAssign for Meters {
+    fn add_assign(&mut self, rhs: Meters) {
+        self.0 += rhs.0;
+    }
+}
+
+impl Neg for Meters {
+    type Output = i64;
+    fn neg(self) -> i64 {
+        -(self.0 as i64)
+    }
+}
+
+#[rustc_dump_operator_output_tys]
+fn test(mut m: Meters, n: Meters, a: u8, b: u8) {
+    let _ = m + n; //~ ERROR overloaded operator output `Area`
+    let _ = -m; //~ ERROR overloaded operator output `i64`
+    m += n; //~ ERROR overloaded operator output `()`
+    let _ = a + b; //~ ERROR builtin operator output `u8`
+    let _ = a < b; //~ ERROR builtin operator output `bool`
+    let _ = !a; //~ ERROR builtin operator output `u8`
+}
+
+// The output of an overloaded operator is normalized.
+#[rustc_dump_operator_output_tys]
+fn generic<T: Add<Output = u16>>(t: T, u: T) {
+    let _ = t + u; //~ ERROR overloaded operator output `u16`
+}
+
+fn main() {}
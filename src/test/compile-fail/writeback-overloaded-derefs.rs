@@ -0,0 +1,26 @@
+// Copyright 2017 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+// Test that deref coercions through a `Deref` impl are told apart from
+// coercions that only dereference builtin pointers.
+
+#![feature(rustc_attrs)]
+
+#[rustc_dump_overloaded_derefs]
+fn test(s: String, r: &&u8, b: Box<u8>, v: Vec<u8>) {
+    let _: &str = &s; //~ ERROR overloaded deref
+    let _: &[u8] = &v; //~ ERROR overloaded deref
+    let _: &u8 = r; //~ ERROR no overloaded deref
+    // Dereferencing a `Box` is builtin.
+    let _: &u8 = &b; //~ ERROR no overloaded deref
+    let _ = &s; //~ ERROR no overloaded deref
+}
+
+fn main() {}
@@ -0,0 +1,35 @@
+// Copyright 2017 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+// Test the place/value classification that writeback records for
+// each expression.
+
+#![feature(rustc_attrs)]
+
+use std::collections::HashMap;
+
+static S: u32 = 0;
+const C: u32 = 0;
+
+fn f() -> u32 { 0 }
+
+#[rustc_dump_place_exprs]
+fn test(x: u32, p: &u32, a: [u32; 2], i: usize, m: HashMap<u32, u32>) {
+    let _x = x; //~ ERROR place expression
+    let _p = *p; //~ ERROR place expression
+    let _a = a[i]; //~ ERROR place expression
+    let _m = m[&0]; //~ ERROR place expression
+    let _s = S; //~ ERROR place expression
+    let _c = C; //~ ERROR value expression
+    let _f = f(); //~ ERROR value expression
+    let _t = (x, x).0; //~ ERROR place expression
+}
+
+fn main() {}
@@ -0,0 +1,29 @@
+// Copyright 2017 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+// Test that the `Range*` struct each range expression was lowered to is
+// available with its resolved type.
+
+#![feature(inclusive_range_syntax, rustc_attrs)]
+
+struct Point { x: u8 }
+
+#[rustc_dump_range_tys]
+fn test(n: u8) {
+    let _ = 1..n; //~ ERROR range `std::ops::Range<u8>`
+    let _ = n..; //~ ERROR range `std::ops::RangeFrom<u8>`
+    let _ = ..10u16; //~ ERROR range `std::ops::RangeTo<u16>`
+    let _ = 'a'..='z'; //~ ERROR range `std::ops::RangeInclusive<char>`
+    let _ = ..=n; //~ ERROR range `std::ops::RangeToInclusive<u8>`
+    let _ = ..;
+    let _ = Point { x: n };
+}
+
+fn main() {}
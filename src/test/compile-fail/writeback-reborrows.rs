@@ -0,0 +1,31 @@
+// Copyright 2017 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+// Test that borrows through an existing reference, explicit or by autoderef,
+// are classified as reborrows, and other borrows as fresh borrows.
+
+#![feature(rustc_attrs)]
+
+struct S {
+    field: u8,
+}
+
+#[rustc_dump_reborrows]
+fn test(r: &mut S, b: Box<S>, v: Vec<u8>) {
+    let local = 1u8;
+    let _ = &local; //~ ERROR fresh borrow
+    let _ = &*r; //~ ERROR reborrow
+    let _ = &mut r.field; //~ ERROR reborrow
+    let _ = &b.field; //~ ERROR fresh borrow
+    let _ = &v[0]; //~ ERROR fresh borrow
+    let _ = &(*r).field; //~ ERROR reborrow
+}
+
+fn main() {}
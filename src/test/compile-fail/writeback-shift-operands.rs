@@ -0,0 +1,43 @@
+// Copyright 2017 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+// Test that shifts of an integer by an integer of another type are
+// treated as builtin operators after writeback, while shifts overloaded
+// for a user type are not.
+
+#![feature(rustc_attrs)]
+
+use std::ops::{Shl, ShlAssign};
+
+struct Bits(u64);
+
+impl Shl<u8> for Bits {
+    type Output = Bits;
+    fn shl(self, rhs: u8) -> Bits {
+        Bits(self.0 << rhs)
+    }
+}
+
+impl ShlAssign<u8> for Bits {
+    fn shl_assign(&mut self, rhs: u8) {
+        self.0 <<= rhs;
+    }
+}
+
+#[rustc_dump_operator_output_tys]
+fn test(mut x: u32, y: u8, z: i64, mut b: Bits) {
+    let _ = x << y; //~ ERROR builtin operator output `u32`
+    let _ = y >> z; //~ ERROR builtin operator output `u8`
+    x <<= y; //~ ERROR builtin operator output `()`
+    let _ = b << y; //~ ERROR overloaded operator output `Bits`
+    b <<= y; //~ ERROR overloaded operator output `()`
+}
+
+fn main() {}
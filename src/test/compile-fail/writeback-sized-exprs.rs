@@ -0,0 +1,26 @@
+// Copyright 2017 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+// compile-flags: -Z precompute-sized
+
+// Test that whether each expression's type is `Sized` is recorded during
+// writeback, using the where-clauses of the body for generic types.
+
+#![feature(rustc_attrs)]
+
+#[rustc_dump_sized_exprs]
+fn test<T, U: ?Sized>(s: &str, n: u32, t: &T, u: &U) {
+    let _ = &*s; //~ ERROR unsized
+    let _ = &n; //~ ERROR sized
+    let _ = &*t; //~ ERROR sized
+    let _ = &*u; //~ ERROR unsized
+}
+
+fn main() {}
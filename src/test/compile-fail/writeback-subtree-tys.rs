@@ -0,0 +1,26 @@
+// Copyright 2017 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+// Test that the resolved types of an expression and everything inside it
+// are reported in pre-order, and that closure bodies are only included
+// when asked for.
+
+#![feature(rustc_attrs)]
+
+#[rustc_dump_subtree_tys]
+fn test(a: u32, b: u8) {
+    let _ = (a + 1, [b]);
+    //~^ ERROR subtree `(u32, [u8; 1])`, `u32`, `u32`, `u32`, `[u8; 1]`, `u8`
+    let _ = Some(|x: u16| x + 1);
+    //~^ ERROR subtree
+    //~| ERROR `u16`, `u16`, `u16`, `u16`
+}
+
+fn main() {}
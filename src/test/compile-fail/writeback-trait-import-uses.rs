@@ -0,0 +1,37 @@
+// Copyright 2017 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+// Test that trait imports needed only for method calls are told apart
+// from those that resolved a path.
+
+#![feature(rustc_attrs)]
+
+mod traits {
+    pub trait A {
+        fn a(&self) {}
+    }
+    impl A for u8 {}
+
+    pub trait B {
+        fn b(&self) {}
+    }
+    impl B for u8 {}
+}
+
+use traits::A; //~ ERROR trait import used for method calls only
+use traits::B; //~ ERROR trait import used for paths
+
+#[rustc_dump_trait_import_uses]
+fn test(x: &Box<u8>) {
+    x.a();
+    u8::b(&1);
+}
+
+fn main() {}
@@ -0,0 +1,35 @@
+// Copyright 2017 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+// Test that the output type of each call along a chain of calls and `?`s
+// is reported along with the type after its `?`, if any.
+
+#![feature(rustc_attrs)]
+
+struct A;
+struct B;
+
+fn a() -> Result<A, ()> { Ok(A) }
+
+impl A {
+    fn b(&self) -> Option<B> { Some(B) }
+    fn c(&self) -> A { A }
+}
+
+#[rustc_dump_try_chain_tys]
+fn test() -> Option<()> {
+    let _ = a().ok()?.b()?;
+    //~^ ERROR chain `std::result::Result<A, ()>` => `std::result::Result<A, ()>`, `std::option
+    let _ = A.c().b()?; //~ ERROR chain `A` => `A`, `std::option::Option<B>` => `B`
+    let _ = A; //~ ERROR chain
+    None
+}
+
+fn main() {}
@@ -0,0 +1,57 @@
+// Copyright 2017 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+// Test that each `?` is classified by the family of its operand.
+
+#![feature(rustc_attrs, try_trait)]
+
+use std::ops::Try;
+
+enum Option {
+    Yes(u8),
+    No,
+}
+
+impl Try for Option {
+    type Ok = u8;
+    type Error = ();
+
+    fn into_result(self) -> Result<u8, ()> {
+        match self {
+            Option::Yes(n) => Ok(n),
+            Option::No => Err(()),
+        }
+    }
+
+    fn from_error(_: ()) -> Self {
+        Option::No
+    }
+
+    fn from_ok(n: u8) -> Self {
+        Option::Yes(n)
+    }
+}
+
+#[rustc_dump_try_kinds]
+fn options(o: ::std::option::Option<u8>) -> ::std::option::Option<u8> {
+    Some(o? + 1) //~ ERROR `?` on an `Option`
+}
+
+#[rustc_dump_try_kinds]
+fn results(r: Result<u8, String>) -> Result<u8, String> {
+    Ok(r? + 1) //~ ERROR `?` on a `Result`
+}
+
+#[rustc_dump_try_kinds]
+fn others(o: Option) -> Option {
+    Option::Yes(o? + 1) //~ ERROR `?` on another `Try` type
+}
+
+fn main() {}
@@ -0,0 +1,33 @@
+// Copyright 2017 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+// Test that `-Z keep-unnormalized-tys` keeps qualified path types as they
+// were written, while the type recorded for the node is normalized.
+
+// compile-flags: -Z keep-unnormalized-tys
+
+#![feature(rustc_attrs)]
+
+trait Tr {
+    type Item;
+}
+
+impl Tr for u8 {
+    type Item = u32;
+}
+
+#[rustc_dump_unnormalized_tys]
+fn test<T: Tr<Item = u32>>() {
+    let x: <u8 as Tr>::Item = 5; //~ ERROR `<u8 as Tr>::Item` normalized to `u32`
+    let y: <T as Tr>::Item = x; //~ ERROR `<T as Tr>::Item` normalized to `u32`
+    let _: Vec<<u8 as Tr>::Item> = vec![y]; //~ ERROR `<u8 as Tr>::Item` normalized to `u32`
+}
+
+fn main() {}
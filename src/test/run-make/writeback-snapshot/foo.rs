@@ -0,0 +1,108 @@
+// Copyright 2017 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+#![feature(rustc_private)]
+
+extern crate rustc;
+extern crate rustc_driver;
+extern crate rustc_lint;
+extern crate rustc_metadata;
+extern crate rustc_errors;
+extern crate rustc_trans;
+
+use rustc::session::{build_session, Session};
+use rustc::session::config::{basic_options, Input, OutputType, OutputTypes};
+use rustc_driver::Compilation;
+use rustc_driver::driver::{compile_input, CompileController, anon_src};
+use rustc_driver::typeck_snapshot::{resolved_type_snapshot, TypeSnapshot};
+use rustc_metadata::cstore::CStore;
+use rustc_errors::registry::Registry;
+
+use std::cell::RefCell;
+use std::path::PathBuf;
+use std::rc::Rc;
+
+fn main() {
+    let src = r#"
+fn main() {
+    let x = vec![1u8];
+}
+fn id(y: u16) -> u16 { y }
+"#;
+
+    let annotated = r#"fn main() {
+    let x = vec![1u8]; //~ TYPE std::vec::Vec<u8>
+    let n = x.len() as u32;
+    //~^ TYPE u32
+    let _ = Some(n); //~ TYPE std::option::Option<u32>
+}
+"#;
+
+    let args: Vec<String> = std::env::args().collect();
+
+    if args.len() < 4 {
+        panic!("expected rustc path");
+    }
+
+    let tmpdir = PathBuf::from(&args[1]);
+
+    let mut sysroot = PathBuf::from(&args[3]);
+    sysroot.pop();
+    sysroot.pop();
+
+    let first = snapshot(src.to_string(), tmpdir.join("out"), sysroot.clone());
+    let second = snapshot(src.to_string(), tmpdir.join("out"), sysroot.clone());
+
+    // The pattern `x` and the whole `let` statement both get the binding's type.
+    assert_eq!(first.types_at("<anon>:3:9: 3:10"), ["std::vec::Vec<u8>"]);
+    assert_eq!(first.types_at("<anon>:3:5: 3:23"), ["std::vec::Vec<u8>"]);
+    // Arguments are reported through their patterns.
+    assert_eq!(first.types_at("<anon>:5:7: 5:8"), ["u16"]);
+
+    assert_eq!(first, second);
+    assert_eq!(first.to_string(), second.to_string());
+
+    // The annotations hold, and changing one of them makes it fail.
+    let annotated_snapshot = snapshot(annotated.to_string(), tmpdir.join("out"), sysroot);
+    assert_eq!(annotated_snapshot.check_type_annotations(annotated), Vec::<String>::new());
+    let failures = annotated_snapshot.check_type_annotations(
+        &annotated.replace("TYPE u32", "TYPE u64"));
+    assert_eq!(failures.len(), 1);
+    assert!(failures[0].starts_with("line 3: expected a node of type `u64`"));
+}
+
+fn basic_sess(sysroot: PathBuf) -> (Session, Rc<CStore>) {
+    let mut opts = basic_options();
+    opts.output_types = OutputTypes::new(&[(OutputType::Metadata, None)]);
+    opts.maybe_sysroot = Some(sysroot);
+
+    let descriptions = Registry::new(&rustc::DIAGNOSTICS);
+    let cstore = Rc::new(CStore::new(Box::new(rustc_trans::LlvmMetadataLoader)));
+    let sess = build_session(opts, None, descriptions);
+    rustc_trans::init(&sess);
+    rustc_lint::register_builtins(&mut sess.lint_store.borrow_mut(), Some(&sess));
+    (sess, cstore)
+}
+
+fn snapshot(code: String, output: PathBuf, sysroot: PathBuf) -> TypeSnapshot {
+    let (sess, cstore) = basic_sess(sysroot);
+    let result = RefCell::new(None);
+    {
+        let mut control = CompileController::basic();
+        control.after_analysis.stop = Compilation::Stop;
+        control.after_analysis.callback = Box::new(|state| {
+            let tcx = state.tcx.unwrap();
+            *result.borrow_mut() = Some(resolved_type_snapshot(tcx));
+        });
+        let input = Input::Str { name: anon_src(), input: code };
+        let _ = compile_input(&sess, &cstore, &input, &None, &Some(output), None, &control);
+    }
+    result.into_inner().expect("analysis did not complete")
+}
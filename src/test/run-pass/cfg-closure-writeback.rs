@@ -0,0 +1,44 @@
+// Copyright 2017 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+// Closures removed by `cfg` must not reach type checking or writeback:
+// each disabled closure below has an argument whose type cannot be
+// inferred, which would be an error if its body were visited. The enabled
+// closures must be fully resolved.
+
+// compile-flags: --cfg enabled
+
+#![feature(stmt_expr_attributes)]
+
+fn apply<F: Fn(u8) -> u8>(f: F) -> u8 {
+    f(1)
+}
+
+fn main() {
+    #[cfg(enabled)]
+    let f = |x| x + 1;
+    #[cfg(not(enabled))]
+    let f = |x| x;
+    assert_eq!(f(1u8), 2);
+
+    #[cfg(not(enabled))]
+    let _g = |y| y.unknown();
+
+    #[cfg_attr(enabled, cfg(any()))]
+    let _h = |z| z;
+
+    let v = [
+        #[cfg(enabled)]
+        apply(|x| x * 2),
+        #[cfg(not(enabled))]
+        apply(|x| x.unknown()),
+    ];
+    assert_eq!(v, [2]);
+}
@@ -0,0 +1,26 @@
+// Copyright 2017 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+// Test that the liberated signature of a closure with a higher-ranked
+// signature is fully resolved by writeback, for both an expected
+// signature coming from a bound and an annotated argument type.
+
+fn call<F>(f: F) -> u8 where F: for<'a> Fn(&'a u8) -> &'a u8 {
+    let x = 22;
+    *f(&x)
+}
+
+fn main() {
+    assert_eq!(call(|x| x), 22);
+    assert_eq!(call(|x: &u8| x), 22);
+
+    let g = |x: &u8| *x + 1;
+    assert_eq!(g(&1), 2);
+}
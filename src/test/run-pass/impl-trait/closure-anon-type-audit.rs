@@ -0,0 +1,36 @@
+// Copyright 2017 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+// Check that the hidden type of an `impl Trait` return type agrees with the
+// type of the closure returned in tail position. Compilers with debug
+// assertions enabled audit this after writeback.
+
+#![feature(conservative_impl_trait)]
+
+fn unit() -> impl Fn() {
+    || {}
+}
+
+fn adder(n: u32) -> impl Fn(u32) -> u32 {
+    move |x| x + n
+}
+
+fn counter<'a>(v: &'a mut Vec<u8>) -> impl FnMut(u8) + 'a {
+    move |x| v.push(x)
+}
+
+fn main() {
+    unit()();
+    assert_eq!(adder(1)(2), 3);
+
+    let mut v = vec![];
+    counter(&mut v)(4);
+    assert_eq!(v, [4]);
+}
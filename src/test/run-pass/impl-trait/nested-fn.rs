@@ -0,0 +1,46 @@
+// Copyright 2016 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+// Check that `impl Trait` return types of functions nested inside other
+// bodies get their hidden types, like those of top-level functions.
+
+#![feature(conservative_impl_trait)]
+
+use std::fmt::Debug;
+
+fn outer() -> Vec<u32> {
+    fn inner() -> impl Iterator<Item = u32> {
+        (1..4).map(|x| x * 2)
+    }
+
+    inner().collect()
+}
+
+fn outer_generic<T: Debug + Clone>(x: T) -> String {
+    fn inner<'a, T: Debug>(x: &'a T) -> impl Debug + 'a {
+        Some(x)
+    }
+
+    let y = x.clone();
+    format!("{:?} {:?}", inner(&x), inner(&y))
+}
+
+fn main() {
+    assert_eq!(outer(), [2, 4, 6]);
+    assert_eq!(outer_generic(1u8), "Some(1) Some(1)");
+
+    let f = || {
+        fn in_closure() -> impl Fn(u8) -> u8 {
+            |x| x + 1
+        }
+        in_closure()(1)
+    };
+    assert_eq!(f(), 2);
+}
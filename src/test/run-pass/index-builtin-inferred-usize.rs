@@ -0,0 +1,26 @@
+// Copyright 2017 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+// Indexing an array with an index only later inferred to be `usize` is
+// lowered as builtin indexing, without the autoref and unsizing that the
+// overloaded `Index` and `IndexMut` impls for slices needed.
+
+fn main() {
+    let mut arr = [1u8, 2, 3];
+    let i = Default::default();
+    arr[i] = 11;
+    let _: usize = i;
+    assert_eq!(arr, [11, 2, 3]);
+
+    let j = Default::default();
+    let x: u8 = arr[j];
+    let _: usize = j;
+    assert_eq!(x, 11);
+}
@@ -0,0 +1,25 @@
+// Copyright 2017 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+// Check that both the annotated and the inferred parts of a partially
+// annotated `let` pattern are resolved.
+
+fn make<T: Default>() -> (u8, T) {
+    (1, T::default())
+}
+
+fn main() {
+    let (a, b): (u8, _) = make();
+    let c: u16 = b;
+    assert_eq!((a, c), (1, 0));
+
+    let (d, (e, f)): (_, (u32, _)) = (2u8, (3, "four"));
+    assert_eq!((d, e, f), (2, 3, "four"));
+}
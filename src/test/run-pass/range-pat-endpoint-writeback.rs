@@ -0,0 +1,54 @@
+// Copyright 2017 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+// Range pattern endpoints are expressions, so writeback resolves them like
+// any other expression. Check that endpoints whose type is only fixed by
+// the scrutinee (or by integer fallback) resolve.
+
+#![feature(dotdoteq_in_patterns)]
+
+const LO: u8 = 1;
+
+trait Bounds {
+    const MAX: Self;
+}
+
+impl Bounds for u16 {
+    const MAX: u16 = 1000;
+}
+
+fn classify(x: u16) -> u32 {
+    match x {
+        0..=9 => 1,
+        10..=<u16 as Bounds>::MAX => 2,
+        _ => 3,
+    }
+}
+
+fn main() {
+    let x = Default::default();
+    let in_range = match x {
+        LO..=9 => true,
+        _ => false,
+    };
+    assert!(!in_range);
+    let _: u8 = x;
+
+    // Both endpoints are unconstrained literals: integer fallback applies.
+    match 5 {
+        1..=3 => unreachable!(),
+        4..=6 => {}
+        _ => unreachable!(),
+    }
+
+    assert_eq!(classify(5), 1);
+    assert_eq!(classify(500), 2);
+    assert_eq!(classify(5000), 3);
+}
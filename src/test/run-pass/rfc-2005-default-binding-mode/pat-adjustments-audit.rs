@@ -0,0 +1,37 @@
+// Copyright 2017 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+// Check that every pattern with default binding mode adjustments also has
+// a resolved type, in `match`, `let`, closure arguments and nested
+// patterns. Compilers with debug assertions enabled audit this after
+// writeback.
+
+#![feature(match_default_bindings)]
+
+struct Pair(u8, Option<u8>);
+
+fn main() {
+    let pair = &Pair(1, Some(2));
+
+    match pair {
+        Pair(a, Some(b)) => assert_eq!(*a + *b, 3),
+        Pair(_, None) => unreachable!(),
+    }
+
+    let Pair(a, _) = pair;
+    assert_eq!(*a, 1);
+
+    let sum = |&(x, y): &(u8, u8), (z, w): &(u8, u8)| x + y + *z + *w;
+    assert_eq!(sum(&(1, 2), &(3, 4)), 10);
+
+    if let Some(Some(n)) = &&Some(Some(5u8)) {
+        assert_eq!(*n, 5);
+    }
+}
@@ -0,0 +1,32 @@
+// Copyright 2017 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+// Shifts whose operands have different integer types are lowered as
+// builtin operators, with no leftover autoref on the left-hand side of
+// compound assignments.
+
+fn main() {
+    let mut x: u32 = 1;
+    let y: u8 = 4;
+    assert_eq!(x << y, 16);
+
+    x <<= y;
+    assert_eq!(x, 16);
+
+    x >>= 2i64;
+    assert_eq!(x, 4);
+
+    let z: i16 = -64;
+    assert_eq!(z >> 3usize, -8);
+
+    let mut arr = [1u64, 2];
+    arr[1] <<= 3u8;
+    assert_eq!(arr, [1, 16]);
+}
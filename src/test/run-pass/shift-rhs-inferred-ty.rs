@@ -0,0 +1,32 @@
+// Copyright 2017 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+// Shifts whose right-hand side only becomes an integer once inference is
+// done are lowered as builtin operators, not as calls to `Shl`/`Shr`.
+
+fn amount() -> u32 { 4 }
+
+fn main() {
+    let n = Default::default();
+    assert_eq!(1u64 << n, 1);
+    let _: u32 = n;
+
+    assert_eq!(1u64 << (amount() + 1), 32);
+    assert_eq!(256u64 >> { let k = amount(); k }, 16);
+
+    let mut x = 1u64;
+    let m = Default::default();
+    x <<= m;
+    let _: u8 = m;
+    assert_eq!(x, 1);
+
+    let v = vec![2u32];
+    assert_eq!(1u64 << v[0], 4);
+}
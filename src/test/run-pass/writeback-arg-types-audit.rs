@@ -0,0 +1,48 @@
+// Copyright 2017 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+// Check that the argument types written back for a body agree with its
+// liberated signature for a variety of argument patterns. Compilers with
+// debug assertions enabled audit this after writeback.
+
+#![feature(match_default_bindings)]
+
+struct Pair<T>(T, T);
+
+impl<T: Copy> Pair<T> {
+    fn first(&self) -> T {
+        self.0
+    }
+
+    fn swap(self, Pair(a, b): Pair<T>) -> (T, T) {
+        (a, b)
+    }
+}
+
+fn patterns<'a, T: Clone>((a, b): (u8, &'a str),
+                          ref c: String,
+                          &d: &u32,
+                          _: Vec<T>,
+                          (e, f): &(T, i64))
+                          -> (u8, &'a str, usize, u32, T, i64) {
+    (a, b, c.len(), d, e.clone(), *f)
+}
+
+fn main() {
+    let p = Pair(1, 2);
+    assert_eq!(p.first(), 1);
+    assert_eq!(p.swap(Pair(3, 4)), (3, 4));
+
+    let r = patterns((1, "a"), String::from("bc"), &4, Vec::<char>::new(), &('x', 5));
+    assert_eq!(r, (1, "a", 2, 4, 'x', 5));
+
+    let add = |x: u8, &y: &u8| x + y;
+    assert_eq!(add(1, &2), 3);
+}
@@ -0,0 +1,44 @@
+// Copyright 2017 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+// Check that the cast kind recorded for each kind of cast agrees with its
+// resolved operand and target types, including casts whose operand type is
+// only known after inference. Compilers with debug assertions enabled audit
+// this after writeback.
+
+#[derive(Clone, Copy)]
+enum E { A = 1 }
+
+fn f() {}
+
+fn main() {
+    let n = 300;
+    let a = [1u8, 2];
+    let x = 5u8;
+    let p = &x as *const u8;
+
+    // numeric, with an operand type that is only known after fallback
+    assert_eq!(n as u8, 44);
+    assert_eq!(2.5f64 as i32, 2);
+    // enum, prim-int and u8-char
+    assert_eq!(E::A as i32, 1);
+    assert_eq!(true as u8 + 'a' as u8, 98);
+    assert_eq!(97u8 as char, 'a');
+    // ptr-ptr, ptr-addr and addr-ptr
+    assert_eq!(p as *const i8 as usize, p as usize);
+    assert_eq!(0usize as *const u8, 0 as *const u8);
+    // array-ptr
+    assert_eq!(unsafe { *(&a as *const u8) }, 1);
+    // fn-ptr-ptr and fn-ptr-addr
+    assert!(f as *const () as usize != 0);
+    assert!(f as usize != 0);
+    // coercion
+    let _ = &x as &u8;
+}
@@ -0,0 +1,56 @@
+// Copyright 2017 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+// Check that the capture that makes each closure `FnMut` or `FnOnce` is
+// recorded with a mode that needs that kind, for closures of each kind,
+// `move` closures and nested closures. Compilers with debug assertions
+// enabled audit this after writeback.
+
+fn main() {
+    let v = vec![1, 2];
+    let mut n = 0;
+
+    // `Fn`, by reference and by value
+    {
+        let len = || v.len();
+        assert_eq!(len(), 2);
+        let double = move |x: i32| x * 2;
+        assert_eq!(double(2), 4);
+    }
+
+    // `FnMut`, by mutable borrow, by unique borrow and by value
+    {
+        let mut incr = || n += 1;
+        incr();
+    }
+    {
+        let r = &mut n;
+        let mut incr_r = || *r += 1;
+        incr_r();
+    }
+    let mut m = 0;
+    let mut incr_m = move || { m += 1; m };
+    assert_eq!(incr_m(), 1);
+    assert_eq!(n, 2);
+
+    // `FnMut` because of a nested closure
+    {
+        let mut outer = || { let mut inner = || n += 1; inner(); };
+        outer();
+    }
+    assert_eq!(n, 3);
+
+    // `FnOnce`, by value, directly and because of a nested closure
+    let w = v.clone();
+    let consume = || w;
+    assert_eq!(consume(), [1, 2]);
+    let outer = || { let inner = move || v; inner() };
+    assert_eq!(outer(), [1, 2]);
+}
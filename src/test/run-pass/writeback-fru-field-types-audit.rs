@@ -0,0 +1,43 @@
+// Copyright 2017 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+// Check that the field types recorded for functional record updates agree
+// with the declared field types under the struct's substs, for generic
+// fields, fields with projection types and fields with lifetimes.
+// Compilers with debug assertions enabled audit this after writeback.
+
+trait Tr {
+    type Assoc;
+}
+
+impl Tr for u8 {
+    type Assoc = u16;
+}
+
+#[derive(Clone, Debug, PartialEq)]
+struct S<'a, T: Tr> {
+    plain: u32,
+    generic: T,
+    projected: T::Assoc,
+    borrowed: &'a str,
+}
+
+fn update<'a, T: Tr + Clone>(s: &S<'a, T>, generic: T) -> S<'a, T>
+    where T::Assoc: Clone
+{
+    S { generic, ..s.clone() }
+}
+
+fn main() {
+    let base = S { plain: 1, generic: 2u8, projected: 3, borrowed: "a" };
+    let s = S { plain: 4, ..base.clone() };
+    assert_eq!(s, S { plain: 4, generic: 2, projected: 3, borrowed: "a" });
+    assert_eq!(update(&base, 5).generic, 5);
+}
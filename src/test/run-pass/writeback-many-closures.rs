@@ -0,0 +1,34 @@
+// Copyright 2017 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+// Test that a body with hundreds of closures taking the same argument
+// types, which writeback resolves over and over, is written back correctly.
+
+macro_rules! closures {
+    ($($x:tt)*) => {
+        vec![$(
+            Box::new(|v: &Vec<Option<(u32, String)>>, _: $x| v.len())
+                as Box<Fn(&Vec<Option<(u32, String)>>, u8) -> usize>,
+        )*]
+    }
+}
+
+macro_rules! many_closures {
+    ($($x:tt)*) => {
+        vec![$(closures!($x $x $x $x $x $x $x $x $x $x $x $x $x $x $x $x),)*]
+    }
+}
+
+fn main() {
+    let all = many_closures!(u8 u8 u8 u8 u8 u8 u8 u8 u8 u8 u8 u8 u8 u8 u8 u8);
+    let v = vec![Some((1, String::new())), None];
+    let total: usize = all.iter().flat_map(|fs| fs.iter()).map(|f| f(&v, 0)).sum();
+    assert_eq!(total, 2 * 16 * 16);
+}
@@ -0,0 +1,46 @@
+// Copyright 2017 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+// Check that no region in the substs recorded for paths and method calls
+// is erased, for generic functions, methods found through autoref and
+// methods with late-bound and early-bound lifetimes. Compilers with debug
+// assertions enabled audit this after writeback.
+
+struct Holder<'a, T: 'a> {
+    value: &'a T,
+}
+
+impl<'a, T> Holder<'a, T> {
+    fn new(value: &'a T) -> Holder<'a, T> {
+        Holder { value }
+    }
+
+    fn get(&self) -> &'a T {
+        self.value
+    }
+
+    fn pick<'b, U>(&self, other: &'b U) -> (&'a T, &'b U) {
+        (self.value, other)
+    }
+}
+
+fn first<'a, T>(xs: &'a [T]) -> &'a T {
+    &xs[0]
+}
+
+fn main() {
+    let v = vec![1, 2, 3];
+    let holder = Holder::new(&v);
+    assert_eq!(*holder.get(), [1, 2, 3]);
+    assert_eq!(holder.pick(&'x'), (&v, &'x'));
+    assert_eq!(*first::<i32>(&v), 1);
+    assert_eq!(v.iter().map(|x| x * 2).collect::<Vec<_>>(), [2, 4, 6]);
+    assert_eq!("a b".split(' ').count(), 2);
+}
@@ -0,0 +1,57 @@
+// Copyright 2017 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+// The writeback audit of binding modes recomputes the default binding mode
+// of each binding from the references its enclosing patterns matched
+// through. Check that it agrees with type checking on the usual cases.
+
+#![feature(match_default_bindings)]
+
+struct Pair {
+    a: u32,
+    b: Option<String>,
+}
+
+fn main() {
+    let mut opt = Some(5u32);
+    match &mut opt {
+        Some(x) => *x += 1,
+        None => {}
+    }
+    assert_eq!(opt, Some(6));
+
+    // `&` under `&mut` gives `ref`, and explicit modes are kept.
+    let pair = Pair { a: 1, b: Some(String::from("b")) };
+    let mut r = &pair;
+    match &mut r {
+        Pair { a, b: Some(s) } => {
+            let _: &u32 = a;
+            let _: &String = s;
+        }
+        Pair { a: ref a, b: None } => {
+            let _: &u32 = a;
+        }
+    }
+
+    let &(x, ref y) = &(1u8, 2u8);
+    let _: u8 = x;
+    let _: &u8 = y;
+
+    let nested = &Some(&mut Some(3u8));
+    if let Some(Some(z)) = nested {
+        let _: &u8 = z;
+    }
+
+    let mut v = vec![(1u32, 2u32)];
+    for (i, j) in &mut v {
+        *i += *j;
+    }
+    assert_eq!(v, [(3, 2)]);
+}
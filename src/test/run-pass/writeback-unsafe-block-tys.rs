@@ -0,0 +1,44 @@
+// Copyright 2017 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+// Check that the type of an `unsafe` block agrees with the adjusted type of
+// its tail expression, in generic functions, through coercions and for
+// diverging tails. Compilers with debug assertions enabled audit this
+// during writeback.
+
+unsafe fn get<T: Clone>(x: &T) -> T {
+    x.clone()
+}
+
+fn generic<T: Clone + Default>(x: &T) -> (T, T) {
+    let a = unsafe { get(x) };
+    let b: T = unsafe { Default::default() };
+    (a, b)
+}
+
+fn first(xs: &[u8; 3]) -> u8 {
+    let slice: &[u8] = unsafe { xs };
+    let f: fn(&u8) -> u8 = unsafe { |x| *x };
+    f(&slice[0])
+}
+
+fn never(b: bool) -> u32 {
+    if b {
+        return 1;
+    }
+    let x: u32 = unsafe { panic!() };
+    x
+}
+
+fn main() {
+    assert_eq!(generic(&5u8), (5, 0));
+    assert_eq!(first(&[7, 8, 9]), 7);
+    assert_eq!(never(true), 1);
+}
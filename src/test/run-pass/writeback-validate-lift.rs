@@ -0,0 +1,34 @@
+// Copyright 2017 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+// Check that `-Z writeback-validate-lift` reports nothing when every value
+// resolved by writeback lifts to the global type context, including values
+// with regions, closures and generators.
+
+// compile-flags: -Z writeback-validate-lift
+
+#![feature(generators, generator_trait)]
+
+use std::ops::Generator;
+
+fn first<'a, T>(xs: &'a [T]) -> Option<&'a T> {
+    xs.iter().next()
+}
+
+fn main() {
+    let v = vec![1u8, 2];
+    let f = |x: &u8| *x + 1;
+    assert_eq!(first(&v).map(f), Some(2));
+
+    let mut g = || {
+        yield 1u32;
+    };
+    g.resume();
+}
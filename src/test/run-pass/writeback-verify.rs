@@ -0,0 +1,44 @@
+// Copyright 2017 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+// Check that `-Z verify-writeback` finds no inference variable in the tables
+// built by writeback, for bodies using closures, generators, method calls,
+// adjustments and functional record updates.
+
+// compile-flags: -Z verify-writeback
+
+#![feature(generators, generator_trait)]
+
+use std::ops::Generator;
+
+#[derive(Clone, Default)]
+struct Config {
+    name: String,
+    level: u32,
+}
+
+fn main() {
+    let v = vec![1u8, 2];
+    let f = |x: &u8| *x + 1;
+    assert_eq!(v.iter().map(f).collect::<Vec<_>>(), [2, 3]);
+
+    let base = Config::default();
+    let config = Config { level: 3, ..base.clone() };
+    assert_eq!(config.level, 3);
+    assert_eq!(config.name.len(), 0);
+
+    let s: &str = &config.name;
+    assert!(s.is_empty());
+
+    let mut g = || {
+        yield 1u32;
+    };
+    g.resume();
+}
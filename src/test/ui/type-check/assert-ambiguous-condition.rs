@@ -0,0 +1,20 @@
+// Copyright 2017 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+// Test that a condition of unknown type in `assert!` is reported at the
+// user's condition, not at the `!` that the macro applies to it.
+
+fn ambiguous<T>() -> T {
+    unimplemented!()
+}
+
+fn main() {
+    assert!(ambiguous());
+}
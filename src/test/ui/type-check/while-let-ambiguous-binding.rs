@@ -0,0 +1,21 @@
+// Copyright 2017 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+// Test that when the type bound by `while let` can't be inferred, the
+// error points at the call that the type comes from, where it can be
+// specified, and not at the binding.
+
+fn next_ambiguous<T>() -> Option<T> {
+    None
+}
+
+fn main() {
+    while let Some(x) = next_ambiguous() {}
+}